@@ -0,0 +1,236 @@
+// семейства матриц и методы, общие для бенчмарков solvers и scaling
+use nalgebra::{DMatrix, DVector};
+
+use lab_2::methods::band::BandMatrix;
+use lab_2::methods::banded::banded_cholesky_solve;
+use lab_2::methods::bicgstab::BiCgStab;
+use lab_2::methods::cholesky::cholesky_solve;
+use lab_2::methods::conjugate_gradient::ConjugateGradient;
+use lab_2::methods::gauss_pivot::gauss_pivot_solve;
+use lab_2::methods::generators::{create_custom_sparse_matrix, create_poisson_2d, create_random_spd, create_vector_f};
+use lab_2::methods::gmres::Gmres;
+use lab_2::methods::jacobi::Jacobi;
+use lab_2::methods::lu::lu_solve;
+use lab_2::methods::matrix::SystemMatrix;
+use lab_2::methods::multigrid::Multigrid;
+use lab_2::methods::preconditioner::Ssor;
+use lab_2::methods::seidel::Seidel;
+use lab_2::methods::solver::{LinearSolver, SolveReport, SolverError};
+use lab_2::methods::sparse::CsrMatrix;
+use lab_2::methods::stopping::StoppingCriterion;
+use lab_2::methods::upper_relaxation::{OmegaSelection, UpperRelaxation};
+
+const TOLERANCE: f64 = 1e-8;
+const MAX_ITERATIONS: usize = 100000;
+
+// плотная копия строится только для небольших n
+const DENSE_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Family {
+    // пятидиагональная матрица пункта д) с a = 10, диагональное преобладание
+    Band,
+    // пятиточечный лапласиан на сетке m x m, n = m^2
+    Poisson,
+    // плотная SPD с cond_2 = 1e3
+    RandomSpd,
+}
+
+pub const FAMILIES: [Family; 3] = [Family::Band, Family::Poisson, Family::RandomSpd];
+
+impl Family {
+    pub fn name(self) -> &'static str {
+        match self {
+            Family::Band => "band",
+            Family::Poisson => "poisson",
+            Family::RandomSpd => "random_spd",
+        }
+    }
+
+    // размеры растут в геометрической прогрессии, наклон по ним считается устойчиво;
+    // у Пуассона n = m^2 с m = 2^k - 1, чтобы подходил многосеточный метод
+    pub fn sizes(self, dense: bool) -> Vec<usize> {
+        match (self, dense) {
+            (Family::Band, true) | (Family::RandomSpd, _) => vec![100, 200, 400, 800],
+            (Family::Band, false) => vec![1000, 4000, 16000, 64000],
+            (Family::Poisson, true) => [7, 15, 31].iter().map(|m| m * m).collect(),
+            (Family::Poisson, false) => [15, 31, 63, 127].iter().map(|m| m * m).collect(),
+        }
+    }
+}
+
+pub struct Problem {
+    pub family: Family,
+    pub matrix: CsrMatrix,
+    pub dense: Option<DMatrix<f64>>,
+    pub f: DVector<f64>,
+}
+
+impl Problem {
+    // для Пуассона берётся ближайшая сетка m x m, размер задачи - m^2
+    pub fn new(family: Family, n: usize) -> Self {
+        let (matrix, dense) = match family {
+            Family::Band => (create_custom_sparse_matrix(n, 10.0), None),
+            Family::Poisson => (create_poisson_2d((n as f64).sqrt().round() as usize), None),
+            Family::RandomSpd => {
+                let dense = create_random_spd(n, 1e3, 42).unwrap();
+                (CsrMatrix::from_dense(&dense), Some(dense))
+            }
+        };
+        let n = matrix.nrows();
+        let dense = dense.or_else(|| (n <= DENSE_LIMIT).then(|| matrix.to_dense()));
+
+        Problem { family, matrix, dense, f: create_vector_f(n) }
+    }
+
+    pub fn n(&self) -> usize {
+        self.f.len()
+    }
+
+    // плотная копия есть только при n <= DENSE_LIMIT
+    fn dense(&self) -> Result<&DMatrix<f64>, SolverError> {
+        self.dense.as_ref().ok_or(SolverError::InvalidParameter { name: "n of a dense method", value: self.n() as f64 })
+    }
+}
+
+fn grid_side(n: usize) -> Result<usize, SolverError> {
+    let m = (n as f64).sqrt().round() as usize;
+    if m * m != n {
+        return Err(SolverError::InvalidParameter { name: "n of the Poisson family (needs n = m^2)", value: n as f64 });
+    }
+    Ok(m)
+}
+
+fn criterion() -> StoppingCriterion {
+    StoppingCriterion::relative_residual(TOLERANCE, MAX_ITERATIONS).quiet()
+}
+
+// итерационный метод должен сойтись, иначе время не имеет смысла
+fn converged(report: SolveReport) -> Result<DVector<f64>, SolverError> {
+    if report.converged {
+        return Ok(report.solution);
+    }
+
+    Err(report.error.unwrap_or(SolverError::NotConverged {
+        iterations: report.iterations,
+        residual: report.residuals.last().copied().unwrap_or(f64::NAN),
+        stop_reason: report.stop_reason,
+    }))
+}
+
+pub struct Method {
+    pub name: &'static str,
+    // плотные методы запускаются на меньших размерах
+    pub dense: bool,
+    pub families: &'static [Family],
+    // ошибка - метод неприменим к задаче или не сошёлся, замер пропускается
+    pub run: fn(&Problem) -> Result<DVector<f64>, SolverError>,
+}
+
+impl Method {
+    pub fn applies_to(&self, family: Family) -> bool {
+        self.families.contains(&family)
+    }
+
+    pub fn runs_on(&self, problem: &Problem) -> bool {
+        self.applies_to(problem.family) && problem.family.sizes(self.dense).contains(&problem.n())
+    }
+}
+
+pub const METHODS: &[Method] = &[
+    Method {
+        name: "gauss_pivot",
+        dense: true,
+        families: &FAMILIES,
+        run: |problem| gauss_pivot_solve(problem.dense()?, &problem.f),
+    },
+    Method {
+        name: "lu",
+        dense: true,
+        families: &FAMILIES,
+        run: |problem| lu_solve(problem.dense()?, &problem.f),
+    },
+    Method {
+        name: "cholesky",
+        dense: true,
+        families: &FAMILIES,
+        run: |problem| Ok(cholesky_solve(problem.dense()?, &problem.f)?),
+    },
+    // ширина ленты у Пуассона m, поэтому время растёт как n^2, а не n
+    Method {
+        name: "banded_cholesky",
+        dense: false,
+        families: &[Family::Band, Family::Poisson],
+        run: |problem| Ok(banded_cholesky_solve(&BandMatrix::from_system(&problem.matrix)?, &problem.f)?),
+    },
+    // стационарные методы на Пуассоне требуют O(m^2) итераций - только ленточное семейство
+    Method {
+        name: "jacobi",
+        dense: false,
+        families: &[Family::Band],
+        run: |problem| converged(Jacobi::new(TOLERANCE, MAX_ITERATIONS).with_criterion(criterion()).solve(&problem.matrix, &problem.f)),
+    },
+    Method {
+        name: "seidel",
+        dense: false,
+        families: &[Family::Band],
+        run: |problem| converged(Seidel::new(TOLERANCE, MAX_ITERATIONS).with_criterion(criterion()).solve(&problem.matrix, &problem.f)),
+    },
+    Method {
+        name: "sor",
+        dense: false,
+        families: &[Family::Band],
+        run: |problem| {
+            let solver = UpperRelaxation::with_selection(OmegaSelection::Fixed(1.1), TOLERANCE, MAX_ITERATIONS).with_criterion(criterion());
+            converged(solver.solve(&problem.matrix, &problem.f))
+        },
+    },
+    Method {
+        name: "cg",
+        dense: false,
+        families: &FAMILIES,
+        run: |problem| converged(ConjugateGradient::new(TOLERANCE, MAX_ITERATIONS).with_criterion(criterion()).solve(&problem.matrix, &problem.f)),
+    },
+    // построение SSOR входит в измеряемое время
+    Method {
+        name: "pcg_ssor",
+        dense: false,
+        families: &[Family::Band, Family::Poisson],
+        run: |problem| {
+            let solver = ConjugateGradient::new(TOLERANCE, MAX_ITERATIONS)
+                .with_criterion(criterion())
+                .with_preconditioner(Box::new(Ssor::new(&problem.matrix, 1.0)?));
+            converged(solver.solve(&problem.matrix, &problem.f))
+        },
+    },
+    Method {
+        name: "bicgstab",
+        dense: false,
+        families: &FAMILIES,
+        run: |problem| converged(BiCgStab::new(TOLERANCE, MAX_ITERATIONS).with_criterion(criterion()).solve(&problem.matrix, &problem.f)),
+    },
+    Method {
+        name: "gmres",
+        dense: false,
+        families: &FAMILIES,
+        run: |problem| converged(Gmres::new(20, TOLERANCE, MAX_ITERATIONS).with_criterion(criterion()).solve(&problem.matrix, &problem.f)),
+    },
+    Method {
+        name: "multigrid",
+        dense: false,
+        families: &[Family::Poisson],
+        run: |problem| {
+            let solver = Multigrid::poisson_2d(grid_side(problem.n())?, TOLERANCE, MAX_ITERATIONS)?.with_criterion(criterion());
+            converged(solver.solve(&problem.matrix, &problem.f))
+        },
+    },
+];
+
+// задачи семейства по возрастанию n: объединение размеров для плотных и разреженных методов
+pub fn problems(family: Family) -> Vec<Problem> {
+    let mut sizes = family.sizes(true);
+    sizes.extend(family.sizes(false));
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes.into_iter().map(|n| Problem::new(family, n)).collect()
+}
@@ -0,0 +1,75 @@
+// cargo bench --features parallel --bench parallel
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use nalgebra::DVector;
+
+use lab_2::methods::bicgstab::BiCgStab;
+use lab_2::methods::generators::{create_custom_matrix, create_poisson_2d};
+use lab_2::methods::jacobi::Jacobi;
+use lab_2::methods::ordering::Ordering;
+use lab_2::methods::parallel::{dot, mul_vec, Execution};
+use lab_2::methods::seidel::Seidel;
+use lab_2::methods::solver::LinearSolver;
+use lab_2::methods::stopping::StoppingCriterion;
+
+const EXECUTIONS: [(&str, Execution); 2] = [("serial", Execution::Serial), ("parallel", Execution::Parallel)];
+
+// фиксированное число итераций, чтобы сравнивать одинаковую работу
+const ITERATIONS: usize = 20;
+
+fn fixed_iterations() -> StoppingCriterion {
+    StoppingCriterion::absolute_residual(0.0, ITERATIONS)
+}
+
+fn kernels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kernels");
+    for m in [300, 1000] {
+        let matrix = create_poisson_2d(m);
+        let x = DVector::from_element(m * m, 1.0);
+        for (label, execution) in EXECUTIONS {
+            group.bench_with_input(BenchmarkId::new(format!("csr_mul_vec_{}", label), m * m), &x, |bench, x| {
+                bench.iter(|| mul_vec(execution, &matrix, x))
+            });
+            group.bench_with_input(BenchmarkId::new(format!("dot_{}", label), m * m), &x, |bench, x| {
+                bench.iter(|| dot(execution, x, x))
+            });
+        }
+    }
+
+    // плотная матрица пункта д): последовательный путь использует nalgebra
+    let n = 2000;
+    let dense = create_custom_matrix(n, 10.0);
+    let x = DVector::from_element(n, 1.0);
+    for (label, execution) in EXECUTIONS {
+        group.bench_with_input(BenchmarkId::new(format!("dense_mul_vec_{}", label), n), &x, |bench, x| {
+            bench.iter(|| mul_vec(execution, &dense, x))
+        });
+    }
+    group.finish();
+}
+
+fn solvers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solvers");
+    group.sample_size(10);
+
+    let m = 500;
+    let matrix = create_poisson_2d(m);
+    let f = DVector::from_element(m * m, 1.0);
+    for (label, execution) in EXECUTIONS {
+        let jacobi = Jacobi::new(0.0, ITERATIONS).with_criterion(fixed_iterations()).without_precheck().with_execution(execution);
+        group.bench_function(BenchmarkId::new(format!("jacobi_{}", label), m * m), |bench| bench.iter(|| jacobi.solve(&matrix, &f)));
+
+        let red_black = Seidel::new(0.0, ITERATIONS)
+            .with_criterion(fixed_iterations())
+            .without_precheck()
+            .with_ordering(Ordering::RedBlack)
+            .with_execution(execution);
+        group.bench_function(BenchmarkId::new(format!("red_black_seidel_{}", label), m * m), |bench| bench.iter(|| red_black.solve(&matrix, &f)));
+
+        let bicgstab = BiCgStab::new(0.0, ITERATIONS).with_criterion(fixed_iterations()).with_execution(execution);
+        group.bench_function(BenchmarkId::new(format!("bicgstab_{}", label), m * m), |bench| bench.iter(|| bicgstab.solve(&matrix, &f)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, kernels, solvers);
+criterion_main!(benches);
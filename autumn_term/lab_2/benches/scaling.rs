@@ -0,0 +1,84 @@
+// cargo bench --bench scaling
+// время решения в зависимости от n и наклон прямой log t = slope * log n + c (МНК);
+// для плотных прямых методов ожидается наклон около 3, для ленточных около 1
+use std::error::Error;
+use std::fs::{create_dir_all, File};
+use std::hint::black_box;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use lab_2::methods::solver::SolverError;
+
+mod common;
+
+use common::{problems, Method, Problem, FAMILIES, METHODS};
+
+// повторы до набора бюджета времени, но не меньше MIN_REPEATS; берётся медиана
+const MIN_REPEATS: usize = 3;
+const TIME_BUDGET: Duration = Duration::from_millis(500);
+
+// ошибка первого же запуска прерывает замер
+fn median_time(method: &Method, problem: &Problem) -> Result<f64, SolverError> {
+    let start = Instant::now();
+    let mut times = Vec::new();
+    while times.len() < MIN_REPEATS || start.elapsed() < TIME_BUDGET {
+        let run = Instant::now();
+        black_box((method.run)(black_box(problem))?);
+        times.push(run.elapsed().as_secs_f64());
+    }
+
+    times.sort_by(f64::total_cmp);
+    Ok(times[times.len() / 2])
+}
+
+// наклон МНК-прямой в логарифмических координатах
+fn log_log_slope(points: &[(usize, f64)]) -> f64 {
+    let logs: Vec<(f64, f64)> = points.iter().map(|&(n, time)| ((n as f64).ln(), time.ln())).collect();
+    let count = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    covariance / variance
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let report_path = "target/scaling/scaling.csv";
+    create_dir_all("target/scaling")?;
+    let mut report = File::create(report_path)?;
+    writeln!(report, "family,method,n,seconds,slope")?;
+
+    let mut summary = Vec::new();
+    for family in FAMILIES {
+        let problems = problems(family);
+        for method in METHODS.iter().filter(|method| method.applies_to(family)) {
+            // неудачный запуск не прерывает весь прогон: размер пропускается с пометкой
+            let points: Vec<(usize, f64)> = problems.iter()
+                .filter(|problem| method.runs_on(problem))
+                .filter_map(|problem| match median_time(method, problem) {
+                    Ok(seconds) => Some((problem.n(), seconds)),
+                    Err(error) => {
+                        println!("Skipping {} on {} (n = {}): {}", method.name, family.name(), problem.n(), error);
+                        None
+                    }
+                })
+                .collect();
+            let slope = log_log_slope(&points);
+
+            for (n, seconds) in &points {
+                writeln!(report, "{},{},{},{:e},{:.3}", family.name(), method.name, n, seconds, slope)?;
+            }
+            summary.push((family.name(), method.name, points, slope));
+        }
+    }
+
+    println!();
+    println!("{:<12} {:<16} {:>8}  time (n: seconds)", "family", "method", "slope");
+    for (family, method, points, slope) in &summary {
+        let times: Vec<String> = points.iter().map(|(n, seconds)| format!("{}: {:.3e}", n, seconds)).collect();
+        println!("{:<12} {:<16} {:>8.2}  {}", family, method, slope, times.join(", "));
+    }
+    println!("Scaling report written to {}", report_path);
+
+    Ok(())
+}
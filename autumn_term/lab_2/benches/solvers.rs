@@ -0,0 +1,35 @@
+// cargo bench --bench solvers [-- <фильтр, например poisson/cg>]
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use std::hint::black_box;
+use std::time::Duration;
+
+mod common;
+
+use common::{problems, FAMILIES, METHODS};
+
+// время до сходимости (относительная невязка 1e-8), а не фиксированное число итераций, как в бенчмарке parallel
+fn solvers(c: &mut Criterion) {
+    for family in FAMILIES {
+        let problems = problems(family);
+        for method in METHODS.iter().filter(|method| method.applies_to(family)) {
+            let mut group = c.benchmark_group(format!("{}/{}", family.name(), method.name));
+            group.sample_size(10).warm_up_time(Duration::from_secs(1)).measurement_time(Duration::from_secs(3));
+
+            for problem in problems.iter().filter(|problem| method.runs_on(problem)) {
+                // неудачный метод не прерывает остальные замеры
+                if let Err(error) = (method.run)(problem) {
+                    println!("Skipping {}/{}/{}: {}", family.name(), method.name, problem.n(), error);
+                    continue;
+                }
+                group.bench_with_input(BenchmarkId::from_parameter(problem.n()), problem, |bench, problem| {
+                    bench.iter(|| (method.run)(black_box(problem)))
+                });
+            }
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
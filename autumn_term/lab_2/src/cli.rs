@@ -0,0 +1,531 @@
+use lab_2::methods::solver::{LinearSolver, SolveReport, SolverError};
+use lab_2::methods::stopping::StoppingCriterion;
+use lab_2::methods::observer::{CsvObserver, JsonLinesObserver, Observers, ProgressBar};
+use lab_2::methods::matrix::SystemMatrix;
+use lab_2::methods::sparse::CsrMatrix;
+use lab_2::methods::preconditioner::Ssor;
+use lab_2::methods::matrix_market::{read_matrix_market, read_matrix_market_vector, write_matrix_market, write_matrix_market_vector};
+use lab_2::methods::plotting::save_residuals_to_csv;
+use lab_2::methods::chart::{ChartOptions, plot_convergence};
+use lab_2::methods::gauss_pivot::GaussPivot;
+use lab_2::methods::lu::{Lu, Pivoting};
+use lab_2::methods::refinement::{IterativeRefinement, Precision};
+use lab_2::methods::cholesky::Cholesky;
+use lab_2::methods::ldlt::Ldlt;
+use lab_2::methods::qr::Qr;
+use lab_2::methods::banded::{BandedLu, BandedCholesky};
+use lab_2::methods::tridiagonal::Thomas;
+use lab_2::methods::jacobi::Jacobi;
+use lab_2::methods::seidel::Seidel;
+use lab_2::methods::upper_relaxation::{UpperRelaxation, OmegaSelection};
+use lab_2::methods::gradient_descent::{GradientDescent, StepStrategy, normal_spectrum_bounds};
+use lab_2::methods::minimal_residuals::MinimalResiduals;
+use lab_2::methods::conjugate_gradient::ConjugateGradient;
+use lab_2::methods::bicgstab::BiCgStab;
+use lab_2::methods::gmres::Gmres;
+use lab_2::methods::generators::{create_custom_sparse_matrix, create_hilbert_matrix, create_vandermonde_matrix, uniform_nodes,
+    create_tridiagonal_toeplitz, create_poisson_1d, create_poisson_2d, create_poisson_3d, create_convection_diffusion_matrix,
+    create_random_spd, create_vector_f, ExactSolution};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use nalgebra::{DMatrix, DVector};
+
+use std::error::Error;
+use std::fs::{File, create_dir_all};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const METHODS: &[&str] = &[
+    "gauss", "lu", "lu-complete", "cholesky", "ldlt", "qr", "banded-lu", "banded-cholesky", "thomas", "refined-lu", "mixed-lu",
+    "jacobi", "seidel", "sor", "gradient-descent", "steepest-descent", "barzilai-borwein", "chebyshev",
+    "minimal-residuals", "cg", "pcg", "bicgstab", "gmres",
+];
+
+// плотные методы при compare --methods all пропускаются для больших матриц
+const DENSE_METHODS: &[&str] = &["gauss", "lu", "lu-complete", "cholesky", "ldlt", "qr", "refined-lu", "mixed-lu", "chebyshev"];
+const DENSE_LIMIT: usize = 5000;
+
+const MATRIX_HELP: &str = "Matrix source: a .mtx or .csv file, or a generator: custom:<n>:<a>, hilbert:<n>, vandermonde:<n>, \
+    toeplitz:<n>:<sub>:<diagonal>:<sup>, poisson1d:<n>, poisson2d:<m>, poisson3d:<m>, convection-diffusion:<n>:<peclet>, \
+    random-spd:<n>:<cond>[:<seed>]";
+const RHS_HELP: &str = "Right-hand side: a .mtx or .csv file, linear (f_i = i + 1), or f = A x* for a known x*: \
+    ones, smooth, random[:<seed>]";
+
+#[derive(Parser)]
+#[command(name = "lab_2", about = "Direct and iterative solvers for linear systems")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    #[command(about = "Solve a system with one method")]
+    Solve(SolveArgs),
+    #[command(about = "Solve a system with several methods and write a comparison table")]
+    Compare(CompareArgs),
+    #[command(about = "Write a generated system to Matrix Market files")]
+    Generate(GenerateArgs),
+    #[command(about = "Run the full lab report (default when no subcommand is given)")]
+    Report(ReportArgs),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CriterionKind {
+    // ||r|| <= tolerance
+    Absolute,
+    // ||r|| <= tolerance * ||f||
+    Relative,
+    // ||x_{k+1} - x_k|| <= tolerance
+    Step,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TraceFormat {
+    // iteration,residual,step_norm,elapsed
+    Csv,
+    // одна JSON-запись на итерацию и итоговая запись с причиной остановки
+    Jsonl,
+}
+
+#[derive(Args)]
+pub struct SystemArgs {
+    #[arg(long, help = MATRIX_HELP)]
+    pub matrix: String,
+    #[arg(long, default_value = "linear", help = RHS_HELP)]
+    pub rhs: String,
+    #[arg(long, default_value_t = 1e-10)]
+    pub tolerance: f64,
+    #[arg(long, default_value_t = 100000)]
+    pub max_iterations: usize,
+    #[arg(long, value_enum, default_value_t = CriterionKind::Relative)]
+    pub criterion: CriterionKind,
+    #[arg(long, default_value_t = 0.01, help = "Step of the plain gradient descent")]
+    pub learning_rate: f64,
+    #[arg(long, default_value_t = 20, help = "GMRES restart length")]
+    pub restart: usize,
+    #[arg(long, help = "SOR relaxation parameter; the optimal one is estimated if omitted")]
+    pub omega: Option<f64>,
+    #[arg(long, default_value = "results")]
+    pub output: PathBuf,
+    #[arg(long, value_enum, help = "Stream every iteration to <output>/<method>_trace.<format>")]
+    pub trace: Option<TraceFormat>,
+    #[arg(long, help = "Show a progress bar on stderr while iterating")]
+    pub progress: bool,
+}
+
+#[derive(Args)]
+pub struct SolveArgs {
+    #[command(flatten)]
+    pub system: SystemArgs,
+    #[arg(long, help = "One of the names listed by compare --help")]
+    pub method: String,
+}
+
+#[derive(Args)]
+pub struct CompareArgs {
+    #[command(flatten)]
+    pub system: SystemArgs,
+    #[arg(long, value_delimiter = ',', default_value = "all", help = methods_help())]
+    pub methods: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    #[arg(long, help = MATRIX_HELP)]
+    pub matrix: String,
+    #[arg(long, default_value = "ones", help = RHS_HELP)]
+    pub rhs: String,
+    #[arg(long, default_value = "system", help = "Writes <name>.mtx, <name>_rhs.mtx and, if known, <name>_exact.mtx")]
+    pub name: String,
+    #[arg(long, default_value = "results")]
+    pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct ReportArgs {
+    #[arg(long, default_value_t = 100, help = "Size of the matrix from task d)")]
+    pub size: usize,
+    #[arg(long, default_value_t = 10.0, help = "Diagonal entry a of the matrix from task d)")]
+    pub diagonal: f64,
+    #[arg(long, default_value = "./../..", help = "Directory that receives results/ and plots_data/")]
+    pub output: PathBuf,
+}
+
+impl Default for ReportArgs {
+    fn default() -> Self {
+        ReportArgs { size: 100, diagonal: 10.0, output: PathBuf::from("./../..") }
+    }
+}
+
+fn methods_help() -> String {
+    format!("Comma-separated methods or all: {}", METHODS.join(", "))
+}
+
+// матрица, правая часть и, если известно, точное решение
+struct LoadedSystem {
+    name: String,
+    matrix: CsrMatrix,
+    f: DVector<f64>,
+    exact_solution: Option<DVector<f64>>,
+}
+
+fn parse_parameter<T: std::str::FromStr>(parts: &[&str], index: usize, spec: &str) -> Result<T, Box<dyn Error>> {
+    let part = parts.get(index).ok_or_else(|| format!("Generator spec '{}' is missing parameter {}", spec, index))?;
+    part.parse().map_err(|_| format!("Cannot parse '{}' in generator spec '{}'", part, spec).into())
+}
+
+fn generate_matrix(spec: &str) -> Result<CsrMatrix, Box<dyn Error>> {
+    let parts: Vec<&str> = spec.split(':').collect();
+    let matrix = match parts[0] {
+        "custom" => create_custom_sparse_matrix(parse_parameter(&parts, 1, spec)?, parse_parameter(&parts, 2, spec)?),
+        "hilbert" => CsrMatrix::from_dense(&create_hilbert_matrix(parse_parameter(&parts, 1, spec)?)),
+        "vandermonde" => CsrMatrix::from_dense(&create_vandermonde_matrix(&uniform_nodes(parse_parameter(&parts, 1, spec)?))),
+        "toeplitz" => create_tridiagonal_toeplitz(parse_parameter(&parts, 1, spec)?, parse_parameter(&parts, 2, spec)?,
+            parse_parameter(&parts, 3, spec)?, parse_parameter(&parts, 4, spec)?),
+        "poisson1d" => create_poisson_1d(parse_parameter(&parts, 1, spec)?),
+        "poisson2d" => create_poisson_2d(parse_parameter(&parts, 1, spec)?),
+        "poisson3d" => create_poisson_3d(parse_parameter(&parts, 1, spec)?),
+        "convection-diffusion" => create_convection_diffusion_matrix(parse_parameter(&parts, 1, spec)?, parse_parameter(&parts, 2, spec)?),
+        "random-spd" => {
+            let (n, cond): (usize, f64) = (parse_parameter(&parts, 1, spec)?, parse_parameter(&parts, 2, spec)?);
+            // cond_2(A) не меньше 1, а для n = 0 не из чего строить ортогональный множитель
+            if n == 0 || cond.is_nan() || cond < 1.0 {
+                return Err(format!("Generator '{}' needs n > 0 and cond >= 1", spec).into());
+            }
+            let seed = if parts.len() > 3 { parse_parameter(&parts, 3, spec)? } else { 42 };
+            CsrMatrix::from_dense(&create_random_spd(n, cond, seed)?)
+        }
+        other => return Err(format!("Unknown matrix generator '{}'", other).into()),
+    };
+
+    Ok(matrix)
+}
+
+// плотная матрица, одна строка CSV на строку матрицы, без заголовка
+fn read_csv_rows(path: &Path) -> Result<Vec<Vec<f64>>, Box<dyn Error>> {
+    let mut reader = csv::ReaderBuilder::new().has_headers(false).flexible(true).trim(csv::Trim::All).from_path(path)?;
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let row = record?.iter().filter(|field| !field.is_empty()).map(|field| field.parse::<f64>()).collect::<Result<Vec<f64>, _>>()?;
+        if !row.is_empty() {
+            rows.push(row);
+        }
+    }
+
+    Ok(rows)
+}
+
+fn read_csv_matrix(path: &Path) -> Result<CsrMatrix, Box<dyn Error>> {
+    let rows = read_csv_rows(path)?;
+    let ncols = rows.first().map_or(0, |row| row.len());
+    if let Some(row) = rows.iter().position(|row| row.len() != ncols) {
+        return Err(format!("{}: row {} has {} entries, expected {}", path.display(), row + 1, rows[row].len(), ncols).into());
+    }
+
+    Ok(CsrMatrix::from_dense(&DMatrix::from_fn(rows.len(), ncols, |i, j| rows[i][j])))
+}
+
+// столбец или строка чисел
+fn read_csv_vector(path: &Path) -> Result<DVector<f64>, Box<dyn Error>> {
+    Ok(DVector::from_vec(read_csv_rows(path)?.concat()))
+}
+
+fn has_extension(source: &str, extension: &str) -> bool {
+    Path::new(source).extension().is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+fn load_matrix(source: &str) -> Result<CsrMatrix, Box<dyn Error>> {
+    if has_extension(source, "mtx") {
+        read_matrix_market(Path::new(source))
+    } else if has_extension(source, "csv") {
+        read_csv_matrix(Path::new(source))
+    } else {
+        generate_matrix(source)
+    }
+}
+
+fn load_system(matrix_source: &str, rhs_source: &str) -> Result<LoadedSystem, Box<dyn Error>> {
+    let matrix = load_matrix(matrix_source)?;
+    if matrix.nrows() != matrix.ncols() {
+        return Err(format!("Matrix is not square ({}x{})", matrix.nrows(), matrix.ncols()).into());
+    }
+
+    let exact_solution = match rhs_source.split(':').collect::<Vec<_>>().as_slice() {
+        _ if has_extension(rhs_source, "mtx") || has_extension(rhs_source, "csv") || rhs_source == "linear" => None,
+        ["ones"] => Some(ExactSolution::Ones),
+        ["smooth"] => Some(ExactSolution::Smooth),
+        ["random"] => Some(ExactSolution::Random(42)),
+        ["random", seed] => Some(ExactSolution::Random(seed.parse().map_err(|_| format!("Cannot parse seed '{}'", seed))?)),
+        _ => return Err(format!("Unknown right-hand side '{}'", rhs_source).into()),
+    };
+
+    // f = A x*, если x* задано
+    let exact_solution = exact_solution.map(|exact| exact.vector(matrix.ncols()));
+    let f = match &exact_solution {
+        Some(x) => matrix.mul_vec(x),
+        None if has_extension(rhs_source, "mtx") => read_matrix_market_vector(Path::new(rhs_source))?,
+        None if has_extension(rhs_source, "csv") => read_csv_vector(Path::new(rhs_source))?,
+        None => create_vector_f(matrix.nrows()),
+    };
+    if f.len() != matrix.nrows() {
+        return Err(format!("Right-hand side has {} entries, matrix has {} rows", f.len(), matrix.nrows()).into());
+    }
+
+    let name = Path::new(matrix_source).file_stem().and_then(|s| s.to_str()).unwrap_or("system").replace(':', "_");
+    Ok(LoadedSystem { name, matrix, f, exact_solution })
+}
+
+fn build_criterion(args: &SystemArgs) -> StoppingCriterion {
+    match args.criterion {
+        CriterionKind::Absolute => StoppingCriterion::absolute_residual(args.tolerance, args.max_iterations),
+        CriterionKind::Relative => StoppingCriterion::relative_residual(args.tolerance, args.max_iterations),
+        CriterionKind::Step => StoppingCriterion::step_norm(args.tolerance, args.max_iterations),
+    }
+}
+
+// имя метода уже проверено check_methods; ошибка построения (например, SSOR при нулевой диагонали)
+// относится только к этому методу и не прерывает compare
+fn build_solver(method: &str, matrix: &CsrMatrix, args: &SystemArgs) -> Result<Box<dyn LinearSolver>, SolverError> {
+    let (tolerance, max_iterations) = (args.tolerance, args.max_iterations);
+    let criterion = build_criterion(args);
+
+    let solver: Box<dyn LinearSolver> = match method {
+        "gauss" => Box::new(GaussPivot),
+        "lu" => Box::new(Lu::new(Pivoting::Partial)),
+        "lu-complete" => Box::new(Lu::new(Pivoting::Complete)),
+        "cholesky" => Box::new(Cholesky),
+        "ldlt" => Box::new(Ldlt),
+        "qr" => Box::new(Qr),
+        "banded-lu" => Box::new(BandedLu),
+        "banded-cholesky" => Box::new(BandedCholesky),
+        "thomas" => Box::new(Thomas),
+        "refined-lu" => Box::new(IterativeRefinement::new(Precision::Double, tolerance, 10)),
+        "mixed-lu" => Box::new(IterativeRefinement::new(Precision::Mixed, tolerance, 10)),
+        "jacobi" => Box::new(Jacobi::new(tolerance, max_iterations).with_criterion(criterion)),
+        "seidel" => Box::new(Seidel::new(tolerance, max_iterations).with_criterion(criterion)),
+        "sor" => {
+            let omega = args.omega.map_or(OmegaSelection::Optimal, OmegaSelection::Fixed);
+            Box::new(UpperRelaxation::with_selection(omega, tolerance, max_iterations).with_criterion(criterion))
+        }
+        "gradient-descent" => Box::new(GradientDescent::new(args.learning_rate, tolerance, max_iterations).with_criterion(criterion)),
+        "steepest-descent" => Box::new(GradientDescent::with_strategy(StepStrategy::ExactLineSearch, tolerance, max_iterations).with_criterion(criterion)),
+        "barzilai-borwein" => Box::new(GradientDescent::with_strategy(StepStrategy::BarzilaiBorwein, tolerance, max_iterations).with_criterion(criterion)),
+        "chebyshev" => {
+            let (lambda_min, lambda_max) = normal_spectrum_bounds(&matrix.to_dense(), 1e-10, 10000);
+            Box::new(GradientDescent::with_strategy(StepStrategy::Chebyshev { lambda_min, lambda_max }, tolerance, max_iterations).with_criterion(criterion))
+        }
+        "minimal-residuals" => Box::new(MinimalResiduals::new(tolerance, max_iterations).with_criterion(criterion)),
+        "cg" => Box::new(ConjugateGradient::new(tolerance, max_iterations).with_criterion(criterion)),
+        "pcg" => Box::new(ConjugateGradient::new(tolerance, max_iterations).with_criterion(criterion).with_preconditioner(Box::new(Ssor::new(matrix, 1.0)?))),
+        "bicgstab" => Box::new(BiCgStab::new(tolerance, max_iterations).with_criterion(criterion)),
+        "gmres" if args.restart == 0 => return Err(SolverError::InvalidParameter { name: "restart", value: 0.0 }),
+        "gmres" => Box::new(Gmres::new(args.restart, tolerance, max_iterations).with_criterion(criterion)),
+        other => unreachable!("Unknown method '{}' passed check_methods", other),
+    };
+
+    Ok(solver)
+}
+
+// решение в <output>/<method>.mtx, история невязок (если есть) в <output>/<method>_residuals.csv
+fn run_method(method: &str, system: &LoadedSystem, args: &SystemArgs) -> Result<(SolveReport, f64), Box<dyn Error>> {
+    let (report, seconds) = match build_solver(method, &system.matrix, args) {
+        Ok(solver) => run_solver(solver.as_ref(), method, system, args)?,
+        Err(error) => (SolveReport::failed(system.f.len(), error), 0.0),
+    };
+
+    write_matrix_market_vector(&args.output.join(format!("{}.mtx", method)), &report.solution)?;
+    if !report.residuals.is_empty() {
+        save_residuals_to_csv(&report.residuals, &args.output.join(format!("{}_residuals.csv", method)).to_string_lossy())?;
+    }
+    if let Some(error) = &report.error {
+        println!("Warning!!! {} method: {}", method, error);
+    }
+    if let Some(omega) = report.omega {
+        println!("{}: omega = {}", method, omega);
+    }
+
+    Ok((report, seconds))
+}
+
+// решение с наблюдателями из --progress и --trace
+fn run_solver(solver: &dyn LinearSolver, method: &str, system: &LoadedSystem, args: &SystemArgs) -> Result<(SolveReport, f64), Box<dyn Error>> {
+    let mut progress = args.progress.then(|| {
+        let bar = ProgressBar::new(args.max_iterations);
+        match args.criterion {
+            CriterionKind::Absolute => bar.with_target(args.tolerance),
+            CriterionKind::Relative => bar.with_target(args.tolerance * system.f.norm()),
+            CriterionKind::Step => bar,
+        }
+    });
+    let trace_path = |extension: &str| args.output.join(format!("{}_trace.{}", method, extension));
+    let mut csv_trace = match args.trace {
+        Some(TraceFormat::Csv) => Some(CsvObserver::create(trace_path("csv"))?.with_details()),
+        _ => None,
+    };
+    let mut json_trace = match args.trace {
+        Some(TraceFormat::Jsonl) => Some(JsonLinesObserver::create(trace_path("jsonl"))?),
+        _ => None,
+    };
+
+    let start = Instant::now();
+    let mut observers = Observers::new().with(&mut progress).with(&mut csv_trace).with(&mut json_trace);
+    let report = solver.solve_observed(&system.matrix, &system.f, &mut observers);
+    let seconds = start.elapsed().as_secs_f64();
+
+    if let Some(trace) = csv_trace {
+        trace.finish()?;
+    }
+    if let Some(trace) = json_trace {
+        trace.finish()?;
+    }
+
+    Ok((report, seconds))
+}
+
+fn describe(system: &LoadedSystem) {
+    println!("{}: n = {}, nnz = {}", system.name, system.matrix.nrows(), system.matrix.nnz());
+}
+
+// опечатка в имени метода обнаруживается до загрузки матрицы и долгих расчётов
+fn check_methods<'a>(methods: impl IntoIterator<Item = &'a str>) -> Result<(), Box<dyn Error>> {
+    for method in methods {
+        if method != "all" && !METHODS.contains(&method) {
+            return Err(format!("Unknown method '{}', expected one of: {}", method, METHODS.join(", ")).into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn solve(args: &SolveArgs) -> Result<(), Box<dyn Error>> {
+    check_methods([args.method.as_str()])?;
+    let system = load_system(&args.system.matrix, &args.system.rhs)?;
+    describe(&system);
+
+    let (report, seconds) = run_method(&args.method, &system, &args.system)?;
+    let residual_norm = (&system.f - system.matrix.mul_vec(&report.solution)).norm();
+    println!("{}: iterations = {}, stop reason = {:?}, residual = {:e}, time = {:.3} s",
+        args.method, report.iterations, report.stop_reason, residual_norm, seconds);
+    if let Some(exact) = &system.exact_solution {
+        println!("forward error = {:e}", (&report.solution - exact).norm() / exact.norm());
+    }
+
+    Ok(())
+}
+
+pub fn compare(args: &CompareArgs) -> Result<(), Box<dyn Error>> {
+    check_methods(args.methods.iter().map(String::as_str))?;
+    let system = load_system(&args.system.matrix, &args.system.rhs)?;
+    describe(&system);
+
+    let n = system.matrix.nrows();
+    let run_all = args.methods.iter().any(|method| method == "all");
+    let methods: Vec<&str> = if run_all {
+        METHODS.iter().copied().filter(|method| {
+            let skip = n > DENSE_LIMIT && DENSE_METHODS.contains(method);
+            if skip {
+                println!("Warning!!! Skipping dense method {} for n = {}", method, n);
+            }
+            !skip
+        }).collect()
+    } else {
+        args.methods.iter().map(String::as_str).collect()
+    };
+
+    create_dir_all(&args.system.output)?;
+    let summary_path = args.system.output.join("compare.csv");
+    let mut summary = File::create(&summary_path)?;
+    writeln!(summary, "method,iterations,stop_reason,residual,forward_error,seconds")?;
+
+    let mut reports = Vec::new();
+    for method in methods {
+        let (report, seconds) = run_method(method, &system, &args.system)?;
+        let residual_norm = (&system.f - system.matrix.mul_vec(&report.solution)).norm();
+        let forward_error = system.exact_solution.as_ref().map(|exact| (&report.solution - exact).norm() / exact.norm());
+        println!("{}: iterations = {}, stop reason = {:?}, residual = {:e}, time = {:.3} s",
+            method, report.iterations, report.stop_reason, residual_norm, seconds);
+
+        let forward_error = forward_error.map(|e| format!("{:e}", e)).unwrap_or_default();
+        writeln!(summary, "{},{},{:?},{:e},{},{}", method, report.iterations, report.stop_reason, residual_norm, forward_error, seconds)?;
+        reports.push((method, report));
+    }
+
+    let reports: Vec<(&str, &SolveReport)> = reports.iter().map(|(method, report)| (*method, report)).collect();
+    let chart_options = ChartOptions::default().with_title(&system.name);
+    plot_convergence(&reports, args.system.output.join("compare.png"), &chart_options)?;
+    plot_convergence(&reports, args.system.output.join("compare.svg"), &chart_options)?;
+
+    println!("Summary and convergence chart written to {}", args.system.output.display());
+    Ok(())
+}
+
+pub fn generate(args: &GenerateArgs) -> Result<(), Box<dyn Error>> {
+    let system = load_system(&args.matrix, &args.rhs)?;
+    describe(&system);
+
+    write_matrix_market(&args.output.join(format!("{}.mtx", args.name)), &system.matrix)?;
+    write_matrix_market_vector(&args.output.join(format!("{}_rhs.mtx", args.name)), &system.f)?;
+    if let Some(exact) = &system.exact_solution {
+        write_matrix_market_vector(&args.output.join(format!("{}_exact.mtx", args.name)), exact)?;
+    }
+
+    println!("System written to {}", args.output.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system_args(method: &str) -> SolveArgs {
+        match Cli::try_parse_from(["lab_2", "solve", "--matrix", "poisson1d:3", "--method", method]).unwrap().command {
+            Some(Command::Solve(args)) => args,
+            _ => unreachable!(),
+        }
+    }
+
+    // ошибка построения относится к одному методу и не прерывает сравнение
+    #[test]
+    fn pcg_with_zero_diagonal_is_a_method_error() {
+        let matrix = CsrMatrix::from_dense(&DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]));
+        let args = system_args("pcg");
+        assert!(matches!(build_solver("pcg", &matrix, &args.system), Err(SolverError::ZeroPivot { row: 0 })));
+    }
+
+    #[test]
+    fn chebyshev_on_singular_matrix_fails_without_panicking() {
+        let matrix = CsrMatrix::from_dense(&DMatrix::from_element(2, 2, 1.0));
+        let args = system_args("chebyshev");
+        let report = build_solver("chebyshev", &matrix, &args.system).unwrap().solve(&matrix, &DVector::from_element(2, 1.0));
+        assert!(matches!(report.error, Some(SolverError::SpectrumBounds { .. })));
+    }
+
+    #[test]
+    fn zero_gmres_restart_is_a_method_error() {
+        let matrix = create_poisson_1d(3);
+        let mut args = system_args("gmres");
+        args.system.restart = 0;
+        assert!(matches!(build_solver("gmres", &matrix, &args.system), Err(SolverError::InvalidParameter { name: "restart", .. })));
+    }
+
+    #[test]
+    fn invalid_random_spd_parameters_are_errors() {
+        assert!(generate_matrix("random-spd:5:0.5").is_err());
+        assert!(generate_matrix("random-spd:0:10").is_err());
+        assert!(generate_matrix("random-spd:5:nan").is_err());
+        assert_eq!(generate_matrix("random-spd:5:10").unwrap().nrows(), 5);
+    }
+
+    #[test]
+    fn every_listed_method_can_be_built() {
+        let matrix = create_poisson_1d(3);
+        let args = system_args("cg");
+        for method in METHODS {
+            assert!(build_solver(method, &matrix, &args.system).is_ok(), "{}", method);
+        }
+        assert!(check_methods(["lu", "cholesky"]).is_ok());
+        assert!(check_methods(["cholesky", "choleski"]).is_err());
+    }
+}
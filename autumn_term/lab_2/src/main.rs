@@ -1,56 +1,56 @@
-mod methods;
-use methods::plotting::save_residuals_to_csv;
-use methods::gauss_pivot::gauss_pivot_solve;
-use methods::lu::lu_solve;
-use methods::jacobi::jacobi_solve;
-use methods::seidel::seidel_solve;
-use methods::upper_relaxation::upper_relaxation_solve;
-use methods::gradient_descent::gradient_descent_solve;
-use methods::minimal_residuals::minimal_residuals_solve;
-use methods::bicgstab::bicgstab_solve;
+use lab_2::methods::plotting::{save_residuals_to_csv, save_preconditioned_residuals_to_csv, save_omega_sweep_to_csv, save_ordering_study_to_csv, save_rows_to_csv, OrderingStudyRow};
+use lab_2::methods::solver::{LinearSolver, SolveReport, SolverError};
+use lab_2::methods::chart::{ChartOptions, plot_convergence};
+use lab_2::methods::stopping::StoppingCriterion;
+use lab_2::methods::matrix::SystemMatrix;
+use lab_2::methods::sparse::CsrMatrix;
+use lab_2::methods::preconditioner::{Preconditioner, PreconditionerSide, JacobiPreconditioner, Ssor, Ilu0, IncompleteCholesky};
+use lab_2::methods::conditioning::{ConditionEstimate, ErrorBounds, two_norm_condition_number};
+use lab_2::methods::diagnostics::diagnose;
+use lab_2::methods::gauss_pivot::GaussPivot;
+use lab_2::methods::lu::{Lu, LuFactorization, Pivoting, lu_solve};
+use lab_2::methods::refinement::{IterativeRefinement, Precision, refined_lu_solve, mixed_precision_lu_solve};
+use lab_2::methods::cholesky::Cholesky;
+use lab_2::methods::ldlt::Ldlt;
+use lab_2::methods::qr::Qr;
+use lab_2::methods::band::BandMatrix;
+use lab_2::methods::banded::{BandedLu, BandedCholesky, banded_cholesky_solve};
+use lab_2::methods::tridiagonal::Thomas;
+use lab_2::methods::jacobi::Jacobi;
+use lab_2::methods::seidel::Seidel;
+use lab_2::methods::ordering::{Ordering, Sweep};
+use lab_2::methods::upper_relaxation::{UpperRelaxation, OmegaSelection, omega_grid, sweep_omega};
+use lab_2::methods::gradient_descent::{GradientDescent, StepStrategy, normal_spectrum_bounds};
+use lab_2::methods::minimal_residuals::MinimalResiduals;
+use lab_2::methods::conjugate_gradient::ConjugateGradient;
+use lab_2::methods::bicgstab::BiCgStab;
+use lab_2::methods::gmres::Gmres;
+use lab_2::methods::multigrid::{Multigrid, Cycle, Smoother};
+use lab_2::methods::eigen::{power_iteration, inverse_iteration, rayleigh_quotient_iteration, lanczos, qr_algorithm};
+use lab_2::methods::generators::{create_custom_matrix, create_custom_sparse_matrix, create_convection_diffusion_matrix,
+    create_poisson_2d_blocks, create_poisson_1d, create_poisson_2d, create_vector_f, standard_test_problems, ExactSolution,
+    create_hilbert_matrix, create_random_spd};
 
 use nalgebra::{DMatrix, DVector};
 
+use clap::Parser;
+
+mod cli;
+
+use cli::{Cli, Command, ReportArgs};
+
 use std::fs;
 use std::io::Write;
 use std::error::Error;
 use std::fs::{OpenOptions, create_dir_all};
 use std::path::Path;
 
-// т.к. решал пункт д)
-fn create_custom_matrix(n: usize, a: f64) -> DMatrix<f64> {
-    let mut matrix = DMatrix::zeros(n, n);
-
-    for i in 0..n {
-        for j in 0..n {
-            if i == j {
-                matrix[(i, j)] = a;  
-            } else if (i as isize - j as isize).abs() <= 2 {
-                matrix[(i, j)] = 1.0;  
-            }
-        }
-    }
-
-    matrix
-}
-
-// т.к. решал пункт д)
-fn create_vector_f(n: usize) -> DVector<f64> {
-    let mut f = DVector::zeros(n);
-
-    for i in 0..n {
-        f[i] = (i + 1) as f64;  
-    }
-
-    f
-}
-
-fn compute_residual_norm(matrix: &DMatrix<f64>, x: &DVector<f64>, f: &DVector<f64>) -> f64 {
-    let residual = f - matrix * x;
+fn compute_residual_norm(matrix: &dyn SystemMatrix, x: &DVector<f64>, f: &DVector<f64>) -> f64 {
+    let residual = f - matrix.mul_vec(x);
     residual.norm()
 }
 
-fn save_solution_to_csv(file_path: &str, method_name: &str, solution: &DVector<f64>) -> Result<(), Box<dyn Error>> {
+fn save_solution_to_csv(file_path: &str, method_name: &str, solution: &DVector<f64>, condition_number: f64, bounds: &ErrorBounds) -> Result<(), Box<dyn Error>> {
 
     if let Some(parent) = Path::new(file_path).parent() {
         create_dir_all(parent)?;
@@ -58,7 +58,8 @@ fn save_solution_to_csv(file_path: &str, method_name: &str, solution: &DVector<f
 
     let mut file = OpenOptions::new().append(true).create(true).open(file_path)?;
 
-    writeln!(file, "{}", method_name)?;
+    writeln!(file, "{}, cond_1 = {:e}, residual_1 = {:e}, backward_error = {:e}, forward_error_bound = {:e}",
+        method_name, condition_number, bounds.residual_norm, bounds.backward_error, bounds.forward_error_bound)?;
 
     let solution_line = solution.iter()
         .map(|x| x.to_string())
@@ -70,57 +71,412 @@ fn save_solution_to_csv(file_path: &str, method_name: &str, solution: &DVector<f
     Ok(())
 }
 
+// библиотека не печатает отказы методов, они приходят в SolveReport::error
+fn warn_on_failure(method: &str, report: &SolveReport) {
+    if let Some(error) = &report.error {
+        println!("Warning!!! {} method: {}", method, error);
+    }
+}
+
+fn build_preconditioners(matrix: &CsrMatrix) -> Result<Vec<Box<dyn Preconditioner>>, SolverError> {
+    Ok(vec![
+        Box::new(JacobiPreconditioner::new(matrix)?),
+        Box::new(Ssor::new(matrix, 1.0)?),
+        Box::new(Ilu0::new(matrix)?),
+        Box::new(IncompleteCholesky::new(matrix)?),
+    ])
+}
+
+// итерации метода с предобуславливателем против того же метода без него
+struct PreconditioningRow {
+    method: String,
+    preconditioner: String,
+    side: String,
+    iterations: usize,
+    baseline_iterations: usize,
+}
+
+fn save_preconditioning_summary(file_path: &str, rows: &[PreconditioningRow]) -> Result<(), Box<dyn Error>> {
+    save_rows_to_csv(rows, "method,preconditioner,side,iterations,iterations_without_preconditioner", file_path, |row| {
+        format!("{},{},{},{},{}", row.method, row.preconditioner, row.side, row.iterations, row.baseline_iterations)
+    })?;
+    Ok(())
+}
+
+struct ForwardErrorRow {
+    problem: String,
+    n: usize,
+    method: String,
+    residual: f64,
+    forward_error: f64,
+}
+
+fn save_forward_errors(file_path: &str, rows: &[ForwardErrorRow]) -> Result<(), Box<dyn Error>> {
+    save_rows_to_csv(rows, "problem,n,method,residual,forward_error", file_path, |row| {
+        format!("{},{},{},{:e},{:e}", row.problem, row.n, row.method, row.residual, row.forward_error)
+    })?;
+    Ok(())
+}
+
 const MACHINE_EPSILON: f64 = 1e-12;
 
 fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Some(Command::Solve(args)) => cli::solve(&args),
+        Some(Command::Compare(args)) => cli::compare(&args),
+        Some(Command::Generate(args)) => cli::generate(&args),
+        Some(Command::Report(args)) => run_report(args.size, args.diagonal, &args.output),
+        None => {
+            let args = ReportArgs::default();
+            run_report(args.size, args.diagonal, &args.output)
+        }
+    };
+
+    if let Err(error) = result {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+// полный прогон лабораторной: все методы на матрице пункта д) и сопутствующие исследования;
+// результаты в <output>/results, истории невязок в <output>/plots_data
+fn run_report(n: usize, a: f64, output: &Path) -> Result<(), Box<dyn Error>> {
+    let result_file = |name: &str| output.join("results").join(name).to_string_lossy().into_owned();
+    let plot_file = |name: &str| output.join("plots_data").join(name).to_string_lossy().into_owned();
 
-    let n = 100;
-    let a = 10.0;
     let matrix = create_custom_matrix(n, a);
     let f = create_vector_f(n);
 
-    let results_file = "./../../results/solutions.csv";
+    let results_file = &result_file("solutions.csv");
     if Path::new(results_file).exists() {
-        fs::remove_file(results_file).expect("Failed to delete previous results file");
+        fs::remove_file(results_file)?;
     }
 
-    let x = gauss_pivot_solve(&matrix, &f);
-    let residual_norm = compute_residual_norm(&matrix, &x, &f);
-    assert!(residual_norm <= MACHINE_EPSILON, "Residual too large for Gauss method");
-    save_solution_to_csv(results_file, "Gauss", &x).expect("Error while writing Gauss method results");
-
-    let x = lu_solve(&matrix, &f);
-    let residual_norm = compute_residual_norm(&matrix, &x, &f);
-    assert!(residual_norm <= MACHINE_EPSILON, "Residual too large for LU method");
-    save_solution_to_csv(results_file, "LU", &x).expect("Error while writing LU method results");
-
     let tolerance_for_iters = 1e-12;
     let max_iterations = 1000000;
+    let learning_rate = 0.01;
+    let gmres_restart = 20;
 
-    let (x, residuals) = jacobi_solve(&matrix, &f, tolerance_for_iters, max_iterations);
-    save_residuals_to_csv(&residuals, "./../../plots_data/jacobi.csv");
-    save_solution_to_csv(results_file, "Jacobi", &x).expect("Error while writing Jacobi method results");
+    // один и тот же критерий для всех итерационных методов, чтобы CSV с невязками были сравнимы
+    let criterion = StoppingCriterion::absolute_residual(tolerance_for_iters, max_iterations)
+        .with_divergence_factor(1e10)
+        .with_stagnation(10000, 1e-3);
 
-    let (x, residuals) = seidel_solve(&matrix, &f, tolerance_for_iters, max_iterations);
-    save_residuals_to_csv(&residuals, "./../../plots_data/seidel.csv");
-    save_solution_to_csv(results_file, "Seidel", &x).expect("Error while writing Seidel method results");
+    let (lambda_min, lambda_max) = normal_spectrum_bounds(&matrix, 1e-10, 10000);
+    let chebyshev = StepStrategy::Chebyshev { lambda_min, lambda_max };
 
-    let omega = 1.00;
-    let (x, residuals) = upper_relaxation_solve(&matrix, &f, omega, tolerance_for_iters, max_iterations);
-    save_residuals_to_csv(&residuals, "./../../plots_data/upper_relaxation.csv");
-    save_solution_to_csv(results_file, "Upper relaxation", &x).expect("Error while writing Upper relaxation method results");
+    let solvers: Vec<(Box<dyn LinearSolver>, Option<String>)> = vec![
+        (Box::new(GaussPivot), None),
+        (Box::new(Lu::new(Pivoting::Partial)), None),
+        (Box::new(IterativeRefinement::new(Precision::Double, MACHINE_EPSILON, 10)), None),
+        (Box::new(IterativeRefinement::new(Precision::Mixed, MACHINE_EPSILON, 10)), None),
+        (Box::new(Cholesky), None),
+        (Box::new(Ldlt), None),
+        (Box::new(Qr), None),
+        (Box::new(BandedLu), None),
+        (Box::new(BandedCholesky), None),
+        (Box::new(Jacobi::new(tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("jacobi.csv"))),
+        (Box::new(Seidel::new(tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("seidel.csv"))),
+        (Box::new(UpperRelaxation::with_selection(OmegaSelection::Optimal, tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("upper_relaxation.csv"))),
+        (Box::new(GradientDescent::new(learning_rate, tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("gradient_descent.csv"))),
+        (Box::new(GradientDescent::with_strategy(StepStrategy::ExactLineSearch, tolerance_for_iters, max_iterations).with_criterion(criterion.clone())),
+            Some(plot_file("steepest_descent.csv"))),
+        (Box::new(GradientDescent::with_strategy(StepStrategy::BarzilaiBorwein, tolerance_for_iters, max_iterations).with_criterion(criterion.clone())),
+            Some(plot_file("barzilai_borwein.csv"))),
+        (Box::new(GradientDescent::with_strategy(chebyshev, tolerance_for_iters, max_iterations).with_criterion(criterion.clone())),
+            Some(plot_file("chebyshev_richardson.csv"))),
+        (Box::new(MinimalResiduals::new(tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("minimals_residual.csv"))),
+        (Box::new(ConjugateGradient::new(tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("conjugate_gradient.csv"))),
+        (Box::new(ConjugateGradient::new(tolerance_for_iters, max_iterations).with_criterion(criterion.clone()).with_preconditioner(Box::new(Ssor::new(&CsrMatrix::from_dense(&matrix), 1.0)?))),
+            Some(plot_file("preconditioned_conjugate_gradient.csv"))),
+        (Box::new(BiCgStab::new(tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("bicgstab.csv"))),
+        (Box::new(Gmres::new(gmres_restart, tolerance_for_iters, max_iterations).with_criterion(criterion.clone())), Some(plot_file("gmres.csv"))),
+    ];
 
-    let learning_rate = 0.01;
-    let (x, residuals) = gradient_descent_solve(&matrix, &f, learning_rate, tolerance_for_iters, max_iterations);
-    save_residuals_to_csv(&residuals, "./../../plots_data/gradient_descent.csv");
-    save_solution_to_csv(results_file, "Gradient descent", &x).expect("Error while writing Gradient descent method results");
+    println!("{:?}", diagnose(&matrix)?);
+
+    let condition = ConditionEstimate::new(&matrix)?;
+    println!("cond_1(A) ≈ {:e} (Hager), cond_2(A) ≈ {:e} (power iteration)",
+        condition.condition_number, two_norm_condition_number(&matrix, 1e-10, 1000)?);
+
+    let mut histories: Vec<(&str, SolveReport)> = Vec::new();
+    for (solver, residuals_file) in &solvers {
+        let name = solver.name();
+        let report = solver.solve(&matrix, &f);
+        warn_on_failure(solver.name(), &report);
+        if let Some(omega) = report.omega {
+            println!("{}: omega = {}", name, omega);
+        }
+
+        match residuals_file {
+            Some(residuals_file) => save_residuals_to_csv(&report.residuals, residuals_file)?,
+            None => {
+                // неточное прямое решение не прерывает отчёт, а отмечается как и прочие отказы
+                let residual_norm = compute_residual_norm(&matrix, &report.solution, &f);
+                if residual_norm > MACHINE_EPSILON {
+                    println!("Warning!!! {} method: residual {:e} exceeds {:e}", name, residual_norm, MACHINE_EPSILON);
+                }
+            }
+        }
+
+        let bounds = condition.error_bounds(&matrix, &report.solution, &f);
+        println!("{}: backward error = {:e}, forward error <= {:e}", name, bounds.backward_error, bounds.forward_error_bound);
+
+        save_solution_to_csv(results_file, name, &report.solution, condition.condition_number, &bounds)?;
+        if residuals_file.is_some() {
+            histories.push((name, report));
+        }
+    }
+
+    // все истории невязок на одном графике, рядом с картинками plots/plotter.py
+    let histories: Vec<(&str, &SolveReport)> = histories.iter().map(|(name, report)| (*name, report)).collect();
+    let chart_options = ChartOptions::default().with_title(&format!("Iterative methods, n = {}", n));
+    plot_convergence(&histories, output.join("plots").join("convergence.png"), &chart_options)?;
+    plot_convergence(&histories, output.join("plots").join("convergence.svg"), &chart_options)?;
+
+    // классическая кривая "число итераций от omega" для метода верхней релаксации
+    let sweep: Vec<(f64, usize, bool)> = sweep_omega(&matrix, &f, &omega_grid(0.1, 1.9, 36), &criterion, Ordering::Natural)?
+        .into_iter()
+        .map(|(omega, report)| (omega, report.iterations, report.converged))
+        .collect();
+    save_omega_sweep_to_csv(&sweep, &result_file("sor_omega_sweep.csv"))?;
+
+    // влияние порядка обхода: естественный, красно-чёрный (только для двудольного графа), жадная раскраска;
+    // прямой проход против симметричного (SGS / SSOR)
+    let ordering_matrices: Vec<(&str, Box<dyn SystemMatrix>)> = vec![
+        ("custom", Box::new(matrix.clone())),
+        ("poisson_1d", Box::new(create_poisson_1d(100))),
+        ("poisson_2d", Box::new(create_poisson_2d(20))),
+    ];
+    let ordering_criterion = StoppingCriterion::relative_residual(1e-10, max_iterations);
+    let mut ordering_study = Vec::new();
+    for (matrix_name, ordering_matrix) in &ordering_matrices {
+        let f_ordering = DVector::from_element(ordering_matrix.nrows(), 1.0);
+        for ordering in [Ordering::Natural, Ordering::RedBlack, Ordering::Multicolor] {
+            for sweep in [Sweep::Forward, Sweep::Symmetric] {
+                let ordered_solvers: Vec<Box<dyn LinearSolver>> = vec![
+                    Box::new(Seidel::new(tolerance_for_iters, max_iterations).with_criterion(ordering_criterion.clone()).with_ordering(ordering).with_sweep(sweep)),
+                    Box::new(UpperRelaxation::new(1.5, tolerance_for_iters, max_iterations).with_criterion(ordering_criterion.clone()).with_ordering(ordering).with_sweep(sweep)),
+                ];
+                for solver in &ordered_solvers {
+                    let report = solver.solve(ordering_matrix.as_ref(), &f_ordering);
+                    warn_on_failure(solver.name(), &report);
+                    ordering_study.push(OrderingStudyRow {
+                        matrix: matrix_name.to_string(),
+                        method: solver.name().to_string(),
+                        ordering,
+                        sweep,
+                        iterations: report.iterations,
+                        converged: report.converged,
+                    });
+                }
+            }
+        }
+    }
+    save_ordering_study_to_csv(&ordering_study, &result_file("ordering.csv"))?;
+
+    // одно разложение на много правых частей
+    let factorization = LuFactorization::new(&matrix, Pivoting::Partial)?;
+    let right_hand_sides = DMatrix::from_fn(n, 100, |i, k| ((i + 1) * (k + 1)) as f64);
+    let solutions = factorization.solve_many(&right_hand_sides)?;
+    let max_residual = (&right_hand_sides - &matrix * &solutions).column_iter().map(|r| r.norm()).fold(0.0, f64::max);
+    println!("LU reused for {} right-hand sides: max residual = {:e}, det(A) = {:e}", right_hand_sides.ncols(), max_residual, factorization.determinant());
+
+    // итерационное уточнение: невязка считается скомпенсированно, поэтому точность не упирается в округления f64;
+    // смешанная точность сходится, только пока cond(A) заметно меньше 1e7
+    let ill_conditioned = [
+        ("Hilbert 10", create_hilbert_matrix(10)),
+        ("random SPD, cond 1e4", create_random_spd(200, 1e4, 42)?),
+        ("random SPD, cond 1e10", create_random_spd(200, 1e10, 42)?),
+    ];
+    for (name, system) in &ill_conditioned {
+        let rhs = system * ExactSolution::Ones.vector(system.nrows());
+        let plain = lu_solve(system, &rhs)?;
+        let refined = refined_lu_solve(system, &rhs, MACHINE_EPSILON, 10)?;
+        let mixed = mixed_precision_lu_solve(system, &rhs, MACHINE_EPSILON, 10)?;
+        println!("{}: LU residual = {:e}, refined = {:e} ({} steps), mixed precision = {:e} ({} steps, {:?})", name,
+            compute_residual_norm(system, &plain, &rhs),
+            compute_residual_norm(system, &refined.solution, &rhs), refined.iterations,
+            compute_residual_norm(system, &mixed.solution, &rhs), mixed.iterations, mixed.stop_reason);
+    }
+
+    // оценка числа итераций CG: k ≈ sqrt(cond) / 2 * ln(2 / eps)
+    let eigenvalues = matrix.clone().symmetric_eigenvalues();
+    let cond = eigenvalues.max() / eigenvalues.min();
+    let relative_tolerance = tolerance_for_iters / f.norm();
+    println!("cond(A) = {:.4}, CG iteration bound = {:.1}", cond, 0.5 * cond.sqrt() * (2.0 / relative_tolerance).ln());
+
+    let sparse_copy = CsrMatrix::from_dense(&matrix);
+    let mut summary = Vec::new();
 
-    let (x, residuals) = minimal_residuals_solve(&matrix, &f, tolerance_for_iters, max_iterations);
-    save_residuals_to_csv(&residuals, "./../../plots_data/minimals_residual.csv");
-    save_solution_to_csv(results_file, "Minimal residual", &x).expect("Error while writing Minimal residual method results");
+    for side in [PreconditionerSide::Left, PreconditionerSide::Right] {
+        for method in ["BiCGSTAB", "Minimal residual"] {
+            let make_solver = |preconditioner: Option<Box<dyn Preconditioner>>| -> Box<dyn LinearSolver> {
+                match (method, preconditioner) {
+                    ("BiCGSTAB", None) => Box::new(BiCgStab::new(tolerance_for_iters, max_iterations)),
+                    ("BiCGSTAB", Some(p)) => Box::new(BiCgStab::new(tolerance_for_iters, max_iterations).with_preconditioner(p, side)),
+                    (_, None) => Box::new(MinimalResiduals::new(tolerance_for_iters, max_iterations)),
+                    (_, Some(p)) => Box::new(MinimalResiduals::new(tolerance_for_iters, max_iterations).with_preconditioner(p, side)),
+                }
+            };
+            let baseline = make_solver(None).solve(&matrix, &f);
+            warn_on_failure(method, &baseline);
 
-    let (x, residuals) = bicgstab_solve(&matrix, &f, tolerance_for_iters, max_iterations);
-    save_residuals_to_csv(&residuals, "./../../plots_data/bicgstab.csv");
-    save_solution_to_csv(results_file, "BiCGSTAB", &x).expect("Error while writing BiCGSTAB method results");
+            for preconditioner in build_preconditioners(&sparse_copy)? {
+                let preconditioner_name = preconditioner.name().to_string();
+                let report = make_solver(Some(preconditioner)).solve(&matrix, &f);
+                warn_on_failure(method, &report);
+
+                let side_name = format!("{:?}", side).to_lowercase();
+                let file_stem = format!("{}_{}_{}", method, preconditioner_name, side_name)
+                    .to_lowercase()
+                    .replace(' ', "_")
+                    .replace(['(', ')'], "");
+                save_preconditioned_residuals_to_csv(&report.residuals, &baseline.residuals, &plot_file(&format!("{}.csv", file_stem)))?;
+
+                summary.push(PreconditioningRow {
+                    method: method.to_string(),
+                    preconditioner: preconditioner_name,
+                    side: side_name,
+                    iterations: report.iterations,
+                    baseline_iterations: baseline.iterations,
+                });
+            }
+        }
+    }
 
+    save_preconditioning_summary(&result_file("preconditioning.csv"), &summary)?;
+
+    // GMRES и BiCGSTAB на несимметричной системе
+    let nonsymmetric_matrix = create_convection_diffusion_matrix(n, 0.5);
+    let f_nonsymmetric = DVector::from_element(n, 1.0);
+    let nonsymmetric_solvers: Vec<(Box<dyn LinearSolver>, String)> = vec![
+        (Box::new(BiCgStab::new(tolerance_for_iters, max_iterations)), plot_file("bicgstab_nonsymmetric.csv")),
+        (Box::new(Gmres::new(gmres_restart, tolerance_for_iters, max_iterations)), plot_file("gmres_nonsymmetric.csv")),
+    ];
+
+    for (solver, residuals_file) in &nonsymmetric_solvers {
+        let report = solver.solve(&nonsymmetric_matrix, &f_nonsymmetric);
+        warn_on_failure(solver.name(), &report);
+        let residual_norm = compute_residual_norm(&nonsymmetric_matrix, &report.solution, &f_nonsymmetric);
+        println!("{} (non-symmetric): iterations = {}, residual = {:e}", solver.name(), report.iterations, residual_norm);
+        save_residuals_to_csv(&report.residuals, residuals_file)?;
+    }
+
+    let n_sparse = 100_000;
+    let sparse_matrix = create_custom_sparse_matrix(n_sparse, a);
+    let f_sparse = create_vector_f(n_sparse);
+    let sparse_tolerance = tolerance_for_iters * f_sparse.norm();
+    println!("Sparse system: n = {}, nnz = {}", n_sparse, sparse_matrix.nnz());
+
+    let sparse_solvers: Vec<Box<dyn LinearSolver>> = vec![
+        Box::new(Jacobi::new(sparse_tolerance, max_iterations)),
+        Box::new(Seidel::new(sparse_tolerance, max_iterations)),
+        Box::new(MinimalResiduals::new(sparse_tolerance, max_iterations)),
+        Box::new(BiCgStab::new(sparse_tolerance, max_iterations)),
+    ];
+
+    for solver in &sparse_solvers {
+        let report = solver.solve(&sparse_matrix, &f_sparse);
+        warn_on_failure(solver.name(), &report);
+        let residual_norm = compute_residual_norm(&sparse_matrix, &report.solution, &f_sparse);
+        println!("{} (sparse): iterations = {}, residual = {:e}", solver.name(), report.iterations, residual_norm);
+    }
+
+    // ленточные прямые методы: O(n b^2) позволяет решать ту же задачу с миллионами неизвестных
+    let n_banded = 2_000_000;
+    let banded_matrix = BandMatrix::from_bands(n_banded, &[(-2, 1.0), (-1, 1.0), (0, a), (1, 1.0), (2, 1.0)]);
+    let f_banded = create_vector_f(n_banded);
+    let x_banded = banded_cholesky_solve(&banded_matrix, &f_banded)?;
+    println!("Banded Cholesky: n = {}, residual = {:e}", n_banded, compute_residual_norm(&banded_matrix, &x_banded, &f_banded));
+
+    let tridiagonal_matrix = create_convection_diffusion_matrix(n_banded, 0.5);
+    let report = Thomas.solve(&tridiagonal_matrix, &f_banded);
+    warn_on_failure("Thomas", &report);
+    println!("Thomas (non-symmetric): n = {}, residual = {:e}", n_banded,
+        compute_residual_norm(&tridiagonal_matrix, &report.solution, &f_banded));
+
+    let grid = 100;
+    let poisson_blocks = create_poisson_2d_blocks(grid)?;
+    let f_poisson = DVector::from_element(grid * grid, 1.0 / ((grid + 1) * (grid + 1)) as f64);
+    let x_poisson = poisson_blocks.solve(&f_poisson)?;
+    println!("Block-tridiagonal 2D Poisson: n = {}, residual = {:e}", grid * grid, (&f_poisson - poisson_blocks.mul_vec(&x_poisson)).norm());
+
+    // многосеточный метод: число циклов не зависит от размера сетки, в отличие от Зейделя и CG
+    let multigrid_criterion = StoppingCriterion::relative_residual(1e-10, max_iterations);
+    for grid in [15, 31, 63, 127] {
+        let poisson = create_poisson_2d(grid);
+        let f_poisson = DVector::from_element(grid * grid, 1.0 / ((grid + 1) * (grid + 1)) as f64);
+        let report = Multigrid::poisson_2d(grid, 1e-10, max_iterations)?.solve(&poisson, &f_poisson);
+        warn_on_failure("Multigrid V-cycle", &report);
+        println!("Multigrid V-cycle on {}x{} grid: {} cycles", grid, grid, report.iterations);
+    }
+
+    let grid = 63;
+    let poisson = create_poisson_2d(grid);
+    let f_poisson = DVector::from_element(grid * grid, 1.0 / ((grid + 1) * (grid + 1)) as f64);
+    let red_black = Smoother::Seidel { ordering: Ordering::RedBlack, sweep: Sweep::Forward };
+    let multigrid_solvers: Vec<(Box<dyn LinearSolver>, String)> = vec![
+        (Box::new(Multigrid::poisson_2d(grid, 1e-10, max_iterations)?), plot_file("multigrid_v.csv")),
+        (Box::new(Multigrid::poisson_2d(grid, 1e-10, max_iterations)?.with_cycle(Cycle::W)), plot_file("multigrid_w.csv")),
+        (Box::new(Multigrid::poisson_2d(grid, 1e-10, max_iterations)?.with_cycle(Cycle::Full)), plot_file("multigrid_full.csv")),
+        (Box::new(Multigrid::poisson_2d(grid, 1e-10, max_iterations)?.with_smoother(red_black)?), plot_file("multigrid_v_red_black.csv")),
+        (Box::new(Seidel::new(tolerance_for_iters, max_iterations).with_criterion(multigrid_criterion.clone())), plot_file("seidel_poisson_2d.csv")),
+        (Box::new(ConjugateGradient::new(tolerance_for_iters, max_iterations).with_criterion(multigrid_criterion.clone())), plot_file("conjugate_gradient_poisson_2d.csv")),
+    ];
+    for (solver, residuals_file) in &multigrid_solvers {
+        let report = solver.solve(&poisson, &f_poisson);
+        warn_on_failure(solver.name(), &report);
+        println!("{} (2D Poisson, n = {}): iterations = {}", solver.name(), grid * grid, report.iterations);
+        save_residuals_to_csv(&report.residuals, residuals_file)?;
+    }
+
+    // собственные значения матрицы пункта д): крайние - степенным методом, Ланцошем и обратными итерациями,
+    // весь спектр - QR-алгоритмом
+    let power = power_iteration(&matrix, 1e-10, max_iterations);
+    let spectrum = qr_algorithm(&matrix, 1e-14, 10000);
+    let lambda_min = spectrum.eigenvalues.iter().map(|&(re, _)| re).fold(f64::INFINITY, f64::min);
+    let ritz = lanczos(&matrix, n, 1e-10);
+    // сдвиг из значения Ритца: младшие собственные значения близки, без сдвига обратные итерации сходятся медленно
+    let inverse = inverse_iteration(&matrix, ritz.ritz_values[0], 1e-10, max_iterations)?;
+    let rayleigh = rayleigh_quotient_iteration(&matrix, &f, 1e-12, 100)?;
+    println!("Power iteration: lambda_max = {} ({} iterations)", power.eigenvalue, power.iterations);
+    println!("Inverse iteration (shift {}): lambda = {} ({} iterations)", ritz.ritz_values[0], inverse.eigenvalue, inverse.iterations);
+    println!("Rayleigh quotient iteration: lambda = {} ({} iterations)", rayleigh.eigenvalue, rayleigh.iterations);
+    println!("Lanczos: lambda in [{}, {}] ({} steps)", ritz.ritz_values[0], ritz.ritz_values.last().unwrap(), ritz.iterations);
+    println!("QR algorithm: lambda_min = {}, {} eigenvalues ({} iterations)", lambda_min, spectrum.eigenvalues.len(), spectrum.iterations);
+    save_residuals_to_csv(&power.residuals, &plot_file("power_iteration_eigen.csv"))?;
+    save_residuals_to_csv(&inverse.residuals, &plot_file("inverse_iteration_eigen.csv"))?;
+    save_residuals_to_csv(&rayleigh.residuals, &plot_file("rayleigh_quotient_eigen.csv"))?;
+    save_residuals_to_csv(&ritz.residuals, &plot_file("lanczos_eigen.csv"))?;
+    save_residuals_to_csv(&spectrum.residuals, &plot_file("qr_algorithm_eigen.csv"))?;
+
+    // набор стандартных задач с известным решением: прямая ошибка вместо одной лишь невязки
+    let mut forward_errors = Vec::new();
+    for problem in standard_test_problems(&ExactSolution::Smooth)? {
+        let size = problem.matrix.nrows();
+        let problem_solvers: Vec<Box<dyn LinearSolver>> = vec![
+            Box::new(Lu::new(Pivoting::Partial)),
+            Box::new(Gmres::new(gmres_restart, tolerance_for_iters, max_iterations).with_criterion(criterion.clone())),
+        ];
+
+        for solver in &problem_solvers {
+            let report = solver.solve(problem.matrix.as_ref(), &problem.f);
+            warn_on_failure(solver.name(), &report);
+            let residual_norm = problem.residual_norm(&report.solution);
+            let forward_error = problem.forward_error(&report.solution).unwrap();
+            println!("{} ({}): n = {}, residual = {:e}, forward error = {:e}", problem.name, solver.name(), size, residual_norm, forward_error);
+            forward_errors.push(ForwardErrorRow {
+                problem: problem.name.clone(),
+                n: size,
+                method: solver.name().to_string(),
+                residual: residual_norm,
+                forward_error,
+            });
+        }
+    }
+    save_forward_errors(&result_file("test_problems.csv"), &forward_errors)?;
+
+    Ok(())
 }
@@ -0,0 +1,182 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::matrix::SystemMatrix;
+use super::solver::{FactorizationError, SolverError};
+
+// ленточное хранение по строкам: строка i хранит столбцы i - lower..=i + upper,
+// элемент (i, j) лежит в data[i * (lower + upper + 1) + j + lower - i]
+#[derive(Debug, Clone)]
+pub struct BandMatrix {
+    n: usize,
+    lower: usize,
+    upper: usize,
+    data: Vec<f64>,
+}
+
+impl BandMatrix {
+    pub fn new(n: usize, lower: usize, upper: usize) -> Self {
+        BandMatrix { n, lower, upper, data: vec![0.0; n * (lower + upper + 1)] }
+    }
+
+    // bands: пары (смещение диагонали, значение), как в CsrMatrix::from_bands
+    pub fn from_bands(n: usize, bands: &[(isize, f64)]) -> Self {
+        let lower = bands.iter().map(|&(offset, _)| (-offset).max(0) as usize).max().unwrap_or(0);
+        let upper = bands.iter().map(|&(offset, _)| offset.max(0) as usize).max().unwrap_or(0);
+        let mut matrix = BandMatrix::new(n, lower, upper);
+
+        for i in 0..n {
+            for &(offset, value) in bands {
+                let j = i as isize + offset;
+                if j >= 0 && (j as usize) < n {
+                    matrix.set(i, j as usize, value);
+                }
+            }
+        }
+
+        matrix
+    }
+
+    // ширина ленты определяется по ненулевым элементам
+    pub fn from_system(matrix: &dyn SystemMatrix) -> Result<Self, FactorizationError> {
+        if matrix.nrows() != matrix.ncols() {
+            return Err(FactorizationError::NotSquare { rows: matrix.nrows(), cols: matrix.ncols() });
+        }
+        let n = matrix.nrows();
+        let rows: Vec<Vec<(usize, f64)>> = (0..n).map(|i| matrix.row_entries(i)).collect();
+
+        let mut lower = 0;
+        let mut upper = 0;
+        for (i, row) in rows.iter().enumerate() {
+            for &(j, _) in row {
+                lower = lower.max(i.saturating_sub(j));
+                upper = upper.max(j.saturating_sub(i));
+            }
+        }
+
+        let mut band = BandMatrix::new(n, lower, upper);
+        for (i, row) in rows.iter().enumerate() {
+            for &(j, value) in row {
+                band.set(i, j, value);
+            }
+        }
+
+        Ok(band)
+    }
+
+    // система для прямых ленточных методов: квадратная матрица и правая часть той же длины
+    pub(crate) fn from_system_with_rhs(matrix: &dyn SystemMatrix, f: &DVector<f64>) -> Result<Self, SolverError> {
+        let band = BandMatrix::from_system(matrix)?;
+        if f.len() != band.n() {
+            return Err(SolverError::InvalidParameter { name: "right-hand side length", value: f.len() as f64 });
+        }
+        Ok(band)
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn lower(&self) -> usize {
+        self.lower
+    }
+
+    pub fn upper(&self) -> usize {
+        self.upper
+    }
+
+    pub fn in_band(&self, i: usize, j: usize) -> bool {
+        j + self.lower >= i && j <= i + self.upper
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        i * (self.lower + self.upper + 1) + j + self.lower - i
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        if self.in_band(i, j) { self.data[self.index(i, j)] } else { 0.0 }
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: f64) {
+        assert!(i < self.n && j < self.n && self.in_band(i, j), "Entry ({}, {}) is outside the band", i, j);
+        let index = self.index(i, j);
+        self.data[index] = value;
+    }
+
+    // столбцы строки i, попадающие в ленту и в матрицу; при n = 0 диапазон пуст
+    pub fn row_range(&self, i: usize) -> std::ops::Range<usize> {
+        i.saturating_sub(self.lower)..(i + self.upper + 1).min(self.n)
+    }
+
+    pub fn amax(&self) -> f64 {
+        self.data.iter().fold(0.0, |max: f64, value| max.max(value.abs()))
+    }
+}
+
+impl SystemMatrix for BandMatrix {
+    fn nrows(&self) -> usize {
+        self.n
+    }
+
+    fn ncols(&self) -> usize {
+        self.n
+    }
+
+    fn mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        DVector::from_fn(self.n, |i, _| self.row_dot(i, x))
+    }
+
+    fn tr_mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        let mut result = DVector::zeros(self.n);
+        for i in 0..self.n {
+            for j in self.row_range(i) {
+                result[j] += self.get(i, j) * x[i];
+            }
+        }
+        result
+    }
+
+    fn row_dot(&self, i: usize, x: &DVector<f64>) -> f64 {
+        self.row_range(i).map(|j| self.get(i, j) * x[j]).sum()
+    }
+
+    fn row_entries(&self, i: usize) -> Vec<(usize, f64)> {
+        self.row_range(i).map(|j| (j, self.get(i, j))).filter(|&(_, value)| value != 0.0).collect()
+    }
+
+    fn diagonal(&self) -> DVector<f64> {
+        DVector::from_fn(self.n, |i, _| self.get(i, i))
+    }
+
+    fn to_dense(&self) -> DMatrix<f64> {
+        DMatrix::from_fn(self.n, self.n, |i, j| self.get(i, j))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::sparse::CsrMatrix;
+
+    #[test]
+    fn matches_dense_storage() {
+        let bands = [(-2, 1.0), (-1, -1.0), (0, 4.0), (1, 2.0)];
+        let band = BandMatrix::from_bands(6, &bands);
+        let dense = CsrMatrix::from_bands(6, &bands).to_dense();
+        let x = DVector::from_fn(6, |i, _| i as f64 + 1.0);
+
+        assert_eq!((band.lower(), band.upper()), (2, 1));
+        assert_eq!(band.to_dense(), dense);
+        assert_eq!(band.mul_vec(&x), &dense * &x);
+        assert_eq!(band.tr_mul_vec(&x), dense.tr_mul(&x));
+        assert_eq!(BandMatrix::from_system(&dense).unwrap().to_dense(), dense);
+    }
+
+    #[test]
+    fn row_range_is_clipped_to_the_matrix() {
+        let band = BandMatrix::new(4, 1, 2);
+        assert_eq!(band.row_range(0), 0..3);
+        assert_eq!(band.row_range(3), 2..4);
+        assert!(BandMatrix::new(0, 1, 1).row_range(0).is_empty());
+    }
+}
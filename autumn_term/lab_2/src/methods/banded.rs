@@ -0,0 +1,271 @@
+use nalgebra::DVector;
+
+use super::band::BandMatrix;
+use super::matrix::SystemMatrix;
+use super::solver::{FactorizationError, LinearSolver, SolveReport};
+
+// LU с частичным выбором по столбцу внутри ленты (как dgbtrf): верхняя ширина U растёт до lower + upper,
+// перестановки применяются к правой части по шагам, стоимость O(n lower (lower + upper))
+#[derive(Debug, Clone)]
+pub struct BandedLuFactorization {
+    lu: BandMatrix,
+    pivots: Vec<usize>,
+}
+
+impl BandedLuFactorization {
+    pub fn new(matrix: &BandMatrix) -> Result<Self, FactorizationError> {
+        let n = matrix.n();
+        let lower = matrix.lower();
+        let mut lu = BandMatrix::new(n, lower, matrix.upper() + lower);
+        for i in 0..n {
+            for j in matrix.row_range(i) {
+                lu.set(i, j, matrix.get(i, j));
+            }
+        }
+        let mut pivots = Vec::with_capacity(n);
+
+        for k in 0..n {
+            let last_row = (k + lower).min(n - 1);
+            let last_col = (k + lu.upper()).min(n - 1);
+
+            let pivot_row = (k..=last_row)
+                .max_by(|&a, &b| lu.get(a, k).abs().total_cmp(&lu.get(b, k).abs()))
+                .unwrap();
+            if lu.get(pivot_row, k) == 0.0 {
+                return Err(FactorizationError::ZeroPivot { row: k });
+            }
+            pivots.push(pivot_row);
+
+            // множители уже посчитанных столбцов не переставляются, поэтому меняем только j >= k
+            if pivot_row != k {
+                for j in k..=last_col {
+                    let temp = lu.get(k, j);
+                    lu.set(k, j, lu.get(pivot_row, j));
+                    lu.set(pivot_row, j, temp);
+                }
+            }
+
+            for i in (k + 1)..=last_row {
+                let factor = lu.get(i, k) / lu.get(k, k);
+                lu.set(i, k, factor);
+                for j in (k + 1)..=last_col {
+                    lu.set(i, j, lu.get(i, j) - factor * lu.get(k, j));
+                }
+            }
+        }
+
+        Ok(BandedLuFactorization { lu, pivots })
+    }
+
+    pub fn solve(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.lu.n();
+        let lower = self.lu.lower();
+        let mut y = b.clone();
+
+        for k in 0..n {
+            y.swap_rows(k, self.pivots[k]);
+            for i in (k + 1)..=(k + lower).min(n - 1) {
+                y[i] -= self.lu.get(i, k) * y[k];
+            }
+        }
+
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..=(i + self.lu.upper()).min(n - 1)).map(|j| self.lu.get(i, j) * y[j]).sum();
+            y[i] = (y[i] - sum) / self.lu.get(i, i);
+        }
+
+        y
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let swaps = self.pivots.iter().enumerate().filter(|&(k, &p)| k != p).count();
+        let sign = if swaps % 2 == 0 { 1.0 } else { -1.0 };
+        sign * (0..self.lu.n()).map(|i| self.lu.get(i, i)).product::<f64>()
+    }
+}
+
+// A = L L^T для симметричной положительно определённой ленточной матрицы, L хранит только нижнюю ленту
+#[derive(Debug, Clone)]
+pub struct BandedCholeskyFactorization {
+    l: BandMatrix,
+}
+
+impl BandedCholeskyFactorization {
+    pub fn new(matrix: &BandMatrix) -> Result<Self, FactorizationError> {
+        check_band_symmetric(matrix)?;
+        let n = matrix.n();
+        let p = matrix.lower();
+        let mut l = BandMatrix::new(n, p, 0);
+
+        for j in 0..n {
+            let first = j.saturating_sub(p);
+            let sum: f64 = (first..j).map(|k| l.get(j, k) * l.get(j, k)).sum();
+            let pivot = matrix.get(j, j) - sum;
+            if pivot <= 0.0 {
+                return Err(FactorizationError::NotPositiveDefinite { row: j, pivot });
+            }
+            l.set(j, j, pivot.sqrt());
+
+            for i in (j + 1)..=(j + p).min(n - 1) {
+                let sum: f64 = (i.saturating_sub(p)..j).map(|k| l.get(i, k) * l.get(j, k)).sum();
+                l.set(i, j, (matrix.get(i, j) - sum) / l.get(j, j));
+            }
+        }
+
+        Ok(BandedCholeskyFactorization { l })
+    }
+
+    pub fn l(&self) -> &BandMatrix {
+        &self.l
+    }
+
+    pub fn solve(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.l.n();
+        let p = self.l.lower();
+
+        let mut y = b.clone();
+        for i in 0..n {
+            let sum: f64 = (i.saturating_sub(p)..i).map(|k| self.l.get(i, k) * y[k]).sum();
+            y[i] = (y[i] - sum) / self.l.get(i, i);
+        }
+
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..=(i + p).min(n - 1)).map(|k| self.l.get(k, i) * y[k]).sum();
+            y[i] = (y[i] - sum) / self.l.get(i, i);
+        }
+
+        y
+    }
+
+    pub fn determinant(&self) -> f64 {
+        (0..self.l.n()).map(|i| self.l.get(i, i)).product::<f64>().powi(2)
+    }
+}
+
+fn check_band_symmetric(matrix: &BandMatrix) -> Result<(), FactorizationError> {
+    let tolerance = 1e-12 * matrix.amax();
+    for i in 0..matrix.n() {
+        for j in matrix.row_range(i) {
+            if (matrix.get(i, j) - matrix.get(j, i)).abs() > tolerance {
+                return Err(FactorizationError::NotSymmetric { row: i, col: j });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub struct BandedLu;
+
+impl LinearSolver for BandedLu {
+    fn name(&self) -> &str {
+        "Banded LU"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        let band = match BandMatrix::from_system_with_rhs(matrix, f) {
+            Ok(band) => band,
+            Err(error) => return SolveReport::failed(f.len(), error),
+        };
+        match BandedLuFactorization::new(&band) {
+            Ok(factorization) => SolveReport::direct(factorization.solve(f)),
+            Err(error) => SolveReport::failed(f.len(), error),
+        }
+    }
+}
+
+pub struct BandedCholesky;
+
+impl LinearSolver for BandedCholesky {
+    fn name(&self) -> &str {
+        "Banded Cholesky"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        let band = match BandMatrix::from_system_with_rhs(matrix, f) {
+            Ok(band) => band,
+            Err(error) => return SolveReport::failed(f.len(), error),
+        };
+        match BandedCholeskyFactorization::new(&band) {
+            Ok(factorization) => SolveReport::direct(factorization.solve(f)),
+            Err(error) => SolveReport::failed(f.len(), error),
+        }
+    }
+}
+
+pub fn banded_lu_solve(matrix: &BandMatrix, b: &DVector<f64>) -> Result<DVector<f64>, FactorizationError> {
+    Ok(BandedLuFactorization::new(matrix)?.solve(b))
+}
+
+pub fn banded_cholesky_solve(matrix: &BandMatrix, b: &DVector<f64>) -> Result<DVector<f64>, FactorizationError> {
+    Ok(BandedCholeskyFactorization::new(matrix)?.solve(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::create_vector_f;
+    use crate::methods::solver::SolverError;
+
+    #[test]
+    fn banded_lu_matches_dense_solution() {
+        // нулевая диагональ требует перестановки строк
+        let matrix = BandMatrix::from_bands(8, &[(-1, 3.0), (0, 0.0), (1, 1.0), (2, -1.0)]);
+        let f = create_vector_f(8);
+        let factorization = BandedLuFactorization::new(&matrix).unwrap();
+        let dense = matrix.to_dense();
+
+        assert!((matrix.mul_vec(&factorization.solve(&f)) - &f).norm() < 1e-12);
+        assert!((factorization.determinant() - dense.determinant()).abs() < 1e-9 * dense.determinant().abs());
+    }
+
+    #[test]
+    fn banded_cholesky_solves_spd_system() {
+        let matrix = BandMatrix::from_bands(10, &[(-2, 1.0), (-1, -1.0), (0, 6.0), (1, -1.0), (2, 1.0)]);
+        let x = DVector::from_fn(10, |i, _| (i as f64).cos());
+        let solution = banded_cholesky_solve(&matrix, &matrix.mul_vec(&x)).unwrap();
+        assert!((solution - x).norm() < 1e-12);
+    }
+
+    #[test]
+    fn banded_cholesky_rejects_bad_matrices() {
+        let nonsymmetric = BandMatrix::from_bands(3, &[(0, 2.0), (1, 1.0)]);
+        assert!(matches!(BandedCholeskyFactorization::new(&nonsymmetric), Err(FactorizationError::NotSymmetric { .. })));
+
+        let indefinite = BandMatrix::from_bands(3, &[(-1, 2.0), (0, 1.0), (1, 2.0)]);
+        assert!(matches!(BandedCholeskyFactorization::new(&indefinite), Err(FactorizationError::NotPositiveDefinite { .. })));
+    }
+
+    #[test]
+    fn singular_matrix_has_zero_pivot() {
+        let matrix = BandMatrix::from_bands(3, &[(0, 0.0), (1, 1.0)]);
+        assert!(matches!(banded_lu_solve(&matrix, &DVector::zeros(3)), Err(FactorizationError::ZeroPivot { row: 0 })));
+    }
+
+    #[test]
+    fn solvers_reject_non_square_matrix_and_wrong_rhs_length() {
+        let report = BandedLu.solve(&DMatrix::from_element(2, 3, 1.0), &DVector::zeros(2));
+        assert!(matches!(report.error, Some(SolverError::Factorization(FactorizationError::NotSquare { .. }))));
+
+        let matrix = BandMatrix::from_bands(4, &[(-1, -1.0), (0, 4.0), (1, -1.0)]).to_dense();
+        let report = BandedCholesky.solve(&matrix, &DVector::zeros(3));
+        assert!(matches!(report.error, Some(SolverError::InvalidParameter { name: "right-hand side length", .. })));
+    }
+
+    #[test]
+    fn empty_system_has_empty_solution() {
+        let matrix = BandMatrix::new(0, 1, 1);
+        assert!(banded_lu_solve(&matrix, &DVector::zeros(0)).unwrap().is_empty());
+        assert!(banded_cholesky_solve(&matrix, &DVector::zeros(0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn zero_rhs_gives_zero_solution() {
+        let matrix = BandMatrix::from_bands(6, &[(-1, -1.0), (0, 4.0), (1, -1.0)]);
+        assert_eq!(banded_lu_solve(&matrix, &DVector::zeros(6)).unwrap(), DVector::zeros(6));
+        assert_eq!(banded_cholesky_solve(&matrix, &DVector::zeros(6)).unwrap(), DVector::zeros(6));
+    }
+}
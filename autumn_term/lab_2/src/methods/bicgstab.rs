@@ -1,57 +1,188 @@
-use nalgebra::{DMatrix, DVector};
-
-pub fn bicgstab_solve(matrix: &DMatrix<f64>, f: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
-    let n = matrix.nrows();
-    let mut x = DVector::zeros(n);  
-    let mut r = f - matrix * &x;     
-    let r_hat = r.clone();       
-    let mut residuals = vec![];      
-    let mut rho_old = 1.0;
-    let mut alpha = 1.0;
-    let mut omega = 1.0;
-    let mut v = DVector::zeros(n);
-    let mut p = DVector::zeros(n);
-
-    for iteration in 0..max_iterations {
-        let rho_new = r_hat.dot(&r);
-        if rho_new.abs() < 1e-50 {
-            break;
-        }
+use nalgebra::DVector;
 
-        if iteration == 0 {
-            p = r.clone();
-        } else {
-            let beta = (rho_new / rho_old) * (alpha / omega);
-            p = &r + beta * (&p - omega * &v);
-        }
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::parallel::{dot, mul_vec, norm, Execution};
+use super::preconditioner::{precondition, Preconditioner, PreconditionerSide, Preconditioning};
+use super::solver::{LinearSolver, SolveReport, SolverError, StopReason};
+use super::stopping::StoppingCriterion;
+
+pub struct BiCgStab {
+    pub criterion: StoppingCriterion,
+    pub preconditioning: Option<Preconditioning>,
+    // умножения на матрицу и скалярные произведения; предобуславливатели остаются последовательными
+    pub execution: Execution,
+}
+
+impl BiCgStab {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        BiCgStab { criterion: StoppingCriterion::absolute_residual(tolerance, max_iterations), preconditioning: None, execution: Execution::Serial }
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    pub fn with_preconditioner(mut self, preconditioner: Box<dyn Preconditioner>, side: PreconditionerSide) -> Self {
+        self.preconditioning = Some(Preconditioning { preconditioner, side });
+        self
+    }
+
+    pub fn with_execution(mut self, execution: Execution) -> Self {
+        self.execution = execution;
+        self
+    }
+}
+
+impl LinearSolver for BiCgStab {
+    fn name(&self) -> &str {
+        "BiCGSTAB"
+    }
+
+    // решается M_L^{-1} A M_R^{-1} y = M_L^{-1} f, x = M_R^{-1} y;
+    // при левом предобуславливании в невязках хранится ||M^{-1} r||
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, f, &mut ())
+    }
 
-        v = matrix * &p;
-        alpha = rho_new / r_hat.dot(&v);
-        let s = &r - alpha * &v;
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let preconditioning = self.preconditioning.as_ref();
+        let execution = self.execution;
+        let operator = |v: &DVector<f64>| {
+            let w = mul_vec(execution, matrix, &precondition(preconditioning, PreconditionerSide::Right, v.clone()));
+            precondition(preconditioning, PreconditionerSide::Left, w)
+        };
+        let solution = |y: DVector<f64>| precondition(preconditioning, PreconditionerSide::Right, y);
 
-        let residual_norm = s.norm();
-        residuals.push(residual_norm);
-        if residual_norm < tolerance {
-            println!("BiCGSTAB method converged in {} iterations", iteration + 1);
-            x += alpha * &p;
-            return (x, residuals);
+        let n = matrix.nrows();
+        let mut x = DVector::zeros(n);  
+        let f = &precondition(preconditioning, PreconditionerSide::Left, f.clone());
+        let mut r = f - operator(&x);     
+        let r_hat = r.clone();       
+        let mut monitor = self.criterion.monitor(f).with_observer(observer);
+        let mut rho_old = 1.0;
+        let mut alpha = 1.0;
+        let mut omega: f64 = 1.0;
+        let mut v = DVector::zeros(n);
+        let mut p = DVector::zeros(n);
+
+        if monitor.initially_converged(norm(execution, &r)) {
+            monitor.announce("BiCGSTAB method", 0, StopReason::Converged);
+            return monitor.finish(solution(x), 0, StopReason::Converged);
         }
 
-        let t = matrix * &s;
-        omega = t.dot(&s) / t.dot(&t);
-        x += alpha * &p + omega * &s;
-        r = &s - omega * &t;
+        for iteration in 0..monitor.max_iterations() {
+            let rho_new = dot(execution, &r_hat, &r);
+            // omega = 0 на прошлой итерации обнуляет и следующий шаг, и знаменатель beta
+            if rho_new.abs() < 1e-50 || omega.abs() < 1e-50 || !omega.is_finite() {
+                return monitor.fail(solution(x), iteration + 1, SolverError::Breakdown { iteration: iteration + 1, rho: rho_new, omega });
+            }
+
+            if iteration == 0 {
+                p = r.clone();
+            } else {
+                let beta = (rho_new / rho_old) * (alpha / omega);
+                p = &r + beta * (&p - omega * &v);
+            }
+
+            v = operator(&p);
+            let r_hat_v = dot(execution, &r_hat, &v);
+            // r_hat ортогонален A p: шаг alpha не определён
+            if r_hat_v.abs() < 1e-50 {
+                return monitor.fail(solution(x), iteration + 1, SolverError::Breakdown { iteration: iteration + 1, rho: rho_new, omega });
+            }
+            alpha = rho_new / r_hat_v;
+            let s = &r - alpha * &v;
+
+            // половинный шаг: x + alpha p, невязка s
+            if let Some(stop_reason) = monitor.check(norm(execution, &s), Some(alpha.abs() * norm(execution, &p))) {
+                monitor.announce("BiCGSTAB method", iteration + 1, stop_reason);
+                x += alpha * &p;
+                return monitor.finish(solution(x), iteration + 1, stop_reason);
+            }
+
+            let t = operator(&s);
+            omega = dot(execution, &t, &s) / dot(execution, &t, &t);
+            let step = alpha * &p + omega * &s;
+            x += &step;
+            r = &s - omega * &t;
 
-        let residual_norm = r.norm();
-        residuals.push(residual_norm);
-        if residual_norm < tolerance {
-            println!("BiCGSTAB method converged in {} iterations", iteration + 1);
-            return (x, residuals);
+            if let Some(stop_reason) = monitor.check(norm(execution, &r), Some(norm(execution, &step))) {
+                monitor.announce("BiCGSTAB method", iteration + 1, stop_reason);
+                return monitor.finish(solution(x), iteration + 1, stop_reason);
+            }
+
+            rho_old = rho_new;
         }
 
-        rho_old = rho_new;
+        let max_iterations = monitor.max_iterations();
+        monitor.announce("BiCGSTAB method", max_iterations, StopReason::MaxIterations);
+        monitor.finish(solution(x), max_iterations, StopReason::MaxIterations)
     }
+}
+
+pub fn bicgstab_solve(matrix: &dyn SystemMatrix, f: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = BiCgStab::new(tolerance, max_iterations).solve(matrix, f);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_vector_f};
 
-    println!("Warning!!! BiCGSTAB method did not converge in {} iterations.", max_iterations);
-    (x, residuals)
+    #[test]
+    fn solves_banded_system() {
+        let matrix = create_custom_sparse_matrix(50, 10.0);
+        let f = create_vector_f(50);
+        let report = BiCgStab::new(1e-10, 1000).solve(&matrix, &f);
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn zero_rhs_converges_immediately() {
+        let matrix = create_custom_sparse_matrix(10, 10.0);
+        let report = BiCgStab::new(1e-10, 100).solve(&matrix, &DVector::zeros(10));
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert_eq!(report.iterations, 0);
+        assert_eq!(report.solution, DVector::zeros(10));
+    }
+
+    #[test]
+    fn zero_diagonal_is_not_a_problem() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let f = DVector::from_vec(vec![1.0, 2.0]);
+        let report = BiCgStab::new(1e-12, 100).solve(&matrix, &f);
+
+        assert!(report.converged);
+        assert!((report.solution - DVector::from_vec(vec![2.0, 1.0])).norm() < 1e-10);
+    }
+
+    #[test]
+    fn breakdown_reports_the_same_iteration() {
+        // для кососимметричной матрицы r_hat^T A r = 0 уже на первом шаге
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, -1.0, 0.0]);
+        let report = BiCgStab::new(1e-12, 100).solve(&matrix, &DVector::from_vec(vec![1.0, 0.0]));
+
+        assert_eq!(report.stop_reason, StopReason::Breakdown);
+        match report.error {
+            Some(SolverError::Breakdown { iteration, .. }) => assert_eq!(iteration, report.iterations),
+            error => panic!("expected breakdown, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn empty_system_converges_immediately() {
+        let report = BiCgStab::new(1e-10, 100).solve(&DMatrix::zeros(0, 0), &DVector::zeros(0));
+
+        assert!(report.converged);
+        assert_eq!(report.iterations, 0);
+        assert!(report.solution.is_empty());
+    }
 }
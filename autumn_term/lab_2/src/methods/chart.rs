@@ -0,0 +1,291 @@
+use plotters::coord::Shift;
+use plotters::element::ComposedElement;
+use plotters::prelude::*;
+
+use std::error::Error;
+use std::fs::create_dir_all;
+use std::path::Path;
+
+use super::solver::{SolveReport, SolverError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisScale {
+    Linear,
+    // по оси откладывается log10 невязки, подписи - степени десяти
+    Logarithmic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    Circle,
+    Triangle,
+    Square,
+    Diamond,
+    Plus,
+}
+
+const MARKERS: [Marker; 5] = [Marker::Circle, Marker::Triangle, Marker::Square, Marker::Diamond, Marker::Plus];
+const MARKER_SIZE: i32 = 5;
+
+impl Marker {
+    // все маркеры - многоугольники в пикселях относительно точки, так у элементов графика один тип
+    fn vertices(self) -> Vec<(i32, i32)> {
+        let s = MARKER_SIZE;
+        match self {
+            Marker::Circle => (0..16).map(|k| {
+                let angle = std::f64::consts::PI * k as f64 / 8.0;
+                ((s as f64 * angle.cos()).round() as i32, (s as f64 * angle.sin()).round() as i32)
+            }).collect(),
+            Marker::Triangle => vec![(0, -s), (s, s - 1), (-s, s - 1)],
+            Marker::Square => vec![(-s + 1, -s + 1), (s - 1, -s + 1), (s - 1, s - 1), (-s + 1, s - 1)],
+            Marker::Diamond => vec![(0, -s), (s, 0), (0, s), (-s, 0)],
+            Marker::Plus => vec![(-1, -s), (1, -s), (1, -1), (s, -1), (s, 1), (1, 1), (1, s), (-1, s), (-1, 1), (-s, 1), (-s, -1), (-1, -1)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartOptions {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    pub size: (u32, u32),
+    pub y_scale: AxisScale,
+    // диапазоны по умолчанию подбираются по данным; y_range задаётся в значениях невязки
+    pub x_range: Option<(usize, usize)>,
+    pub y_range: Option<(f64, f64)>,
+    // маркеры ставятся примерно равномерно, чтобы длинные истории не сливались в сплошную полосу
+    pub markers_per_series: usize,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        ChartOptions {
+            title: "Residual convergence".to_string(),
+            x_label: "Iteration".to_string(),
+            y_label: "Residual".to_string(),
+            size: (1024, 768),
+            y_scale: AxisScale::Logarithmic,
+            x_range: None,
+            y_range: None,
+            markers_per_series: 12,
+        }
+    }
+}
+
+impl ChartOptions {
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    pub fn with_labels(mut self, x_label: &str, y_label: &str) -> Self {
+        self.x_label = x_label.to_string();
+        self.y_label = y_label.to_string();
+        self
+    }
+
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub fn with_y_scale(mut self, y_scale: AxisScale) -> Self {
+        self.y_scale = y_scale;
+        self
+    }
+
+    pub fn with_x_range(mut self, from: usize, to: usize) -> Self {
+        self.x_range = Some((from, to));
+        self
+    }
+
+    pub fn with_y_range(mut self, from: f64, to: f64) -> Self {
+        self.y_range = Some((from, to));
+        self
+    }
+
+    pub fn with_markers_per_series(mut self, markers: usize) -> Self {
+        self.markers_per_series = markers;
+        self
+    }
+
+    // на логарифмической шкале нулевые и нечисловые невязки не изображаются
+    fn transform(&self, residual: f64) -> Option<f64> {
+        match self.y_scale {
+            AxisScale::Linear => residual.is_finite().then_some(residual),
+            AxisScale::Logarithmic => (residual.is_finite() && residual > 0.0).then(|| residual.log10()),
+        }
+    }
+}
+
+// один график со всеми историями невязок; формат по расширению: .svg - SVG, иначе PNG.
+// прямые методы (без истории) пропускаются
+pub fn plot_convergence(reports: &[(&str, &SolveReport)], path: impl AsRef<Path>, options: &ChartOptions) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    let series: Vec<(&str, Vec<(usize, f64)>)> = reports.iter()
+        .filter(|(_, report)| !report.residuals.is_empty())
+        .map(|(name, report)| {
+            let points = report.residuals.iter().enumerate()
+                .filter_map(|(iteration, &residual)| options.transform(residual).map(|y| (iteration, y)))
+                .collect();
+            (*name, points)
+        })
+        .collect();
+
+    if path.extension().is_some_and(|extension| extension == "svg") {
+        draw_chart(SVGBackend::new(path, options.size).into_drawing_area(), &series, options)
+    } else {
+        draw_chart(BitMapBackend::new(path, options.size).into_drawing_area(), &series, options)
+    }
+}
+
+fn draw_chart<DB: DrawingBackend>(root: DrawingArea<DB, Shift>, series: &[(&str, Vec<(usize, f64)>)], options: &ChartOptions) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    // заданная граница должна изображаться на выбранной шкале: на логарифмической - только положительные
+    let bound = |value: f64| options.transform(value).ok_or(SolverError::InvalidParameter { name: "y_range", value });
+    let y_bounds = match options.y_range {
+        Some((from, to)) => Some((bound(from)?, bound(to)?)),
+        None => None,
+    };
+
+    root.fill(&WHITE)?;
+
+    let points = || series.iter().flat_map(|(_, points)| points.iter());
+    let x_range = options.x_range.unwrap_or_else(|| (0, points().map(|&(x, _)| x + 1).max().unwrap_or(1)));
+    let y_range = match y_bounds {
+        Some(bounds) => bounds,
+        None => {
+            let min = points().map(|&(_, y)| y).fold(f64::INFINITY, f64::min);
+            let max = points().map(|&(_, y)| y).fold(f64::NEG_INFINITY, f64::max);
+            match options.y_scale {
+                _ if min > max => (0.0, 1.0),
+                AxisScale::Logarithmic => (min.floor(), max.ceil().max(min.floor() + 1.0)),
+                AxisScale::Linear => (min.min(0.0), if max > 0.0 { 1.05 * max } else { 1.0 }),
+            }
+        }
+    };
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(&options.title, ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(70)
+        .build_cartesian_2d(x_range.0..x_range.1, y_range.0..y_range.1)?;
+
+    let y_scale = options.y_scale;
+    chart.configure_mesh()
+        .x_desc(options.x_label.as_str())
+        .y_desc(options.y_label.as_str())
+        .y_label_formatter(&|y| match y_scale {
+            AxisScale::Logarithmic => format!("{:.0e}", 10f64.powf(*y)),
+            AxisScale::Linear => format!("{:.1e}", y),
+        })
+        .draw()?;
+
+    for (index, (name, points)) in series.iter().enumerate() {
+        let style = Palette99::pick(index).stroke_width(2);
+        let marker = MARKERS[index % MARKERS.len()];
+        let visible = points.iter().copied().filter(|&(x, y)| {
+            (x_range.0..x_range.1).contains(&x) && y >= y_range.0 && y <= y_range.1
+        });
+
+        chart.draw_series(LineSeries::new(points.iter().copied(), style))?;
+
+        let visible: Vec<(usize, f64)> = visible.collect();
+        let stride = (visible.len() / options.markers_per_series.max(1)).max(1);
+        chart.draw_series(visible.iter().step_by(stride).map(|&point| marker_element(marker, point, style, 0)))?
+            .label(*name)
+            .legend(move |point| marker_element(marker, point, style, 10));
+    }
+
+    chart.configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
+
+type MarkerElement<C, DB> = ComposedElement<C, DB, PathElement<(i32, i32)>, Polygon<(i32, i32)>>;
+
+// маркер с горизонтальным отрезком полуширины line (в легенде); на самом графике line = 0
+fn marker_element<C, DB: DrawingBackend>(marker: Marker, at: C, style: ShapeStyle, line: i32) -> MarkerElement<C, DB> {
+    EmptyElement::at(at) + PathElement::new(vec![(-line, 0), (line, 0)], style) + Polygon::new(marker.vertices(), style.filled())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_vector_f};
+    use crate::methods::jacobi::Jacobi;
+    use crate::methods::solver::LinearSolver;
+
+    fn chart_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join("lab_2_chart_tests").join(name)
+    }
+
+    fn jacobi_report() -> SolveReport {
+        Jacobi::new(1e-10, 1000).solve(&create_custom_sparse_matrix(20, 10.0), &create_vector_f(20))
+    }
+
+    #[test]
+    fn logarithmic_scale_skips_non_positive_residuals() {
+        let options = ChartOptions::default();
+        assert_eq!(options.transform(100.0), Some(2.0));
+        assert_eq!(options.transform(0.0), None);
+        assert_eq!(options.transform(-1.0), None);
+        assert_eq!(options.transform(f64::NAN), None);
+
+        let linear = options.with_y_scale(AxisScale::Linear);
+        assert_eq!(linear.transform(-1.0), Some(-1.0));
+        assert_eq!(linear.transform(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn svg_chart_has_a_legend_entry_per_iterative_method() {
+        let path = chart_path("convergence.svg");
+        let jacobi = jacobi_report();
+        let direct = SolveReport::failed(20, SolverError::Singular);
+        plot_convergence(&[("Jacobi", &jacobi), ("Gauss", &direct)], &path, &ChartOptions::default()).unwrap();
+
+        let svg = fs::read_to_string(&path).unwrap();
+        assert!(svg.contains("Jacobi"));
+        assert!(!svg.contains("Gauss"));
+    }
+
+    #[test]
+    fn explicit_ranges_and_empty_chart() {
+        let jacobi = jacobi_report();
+        let options = ChartOptions::default().with_x_range(0, 5).with_y_range(1e-3, 1e2);
+        plot_convergence(&[("Jacobi", &jacobi)], chart_path("ranges.svg"), &options).unwrap();
+
+        plot_convergence(&[], chart_path("empty.svg"), &ChartOptions::default().with_y_scale(AxisScale::Linear)).unwrap();
+    }
+
+    #[test]
+    fn non_positive_bound_on_logarithmic_scale_is_an_error() {
+        let jacobi = jacobi_report();
+        for (from, to) in [(0.0, 1.0), (-1.0, 1.0), (1e-3, 0.0)] {
+            let options = ChartOptions::default().with_y_range(from, to);
+            let error = plot_convergence(&[("Jacobi", &jacobi)], chart_path("invalid.svg"), &options).unwrap_err();
+            assert!(matches!(error.downcast_ref::<SolverError>(), Some(SolverError::InvalidParameter { name: "y_range", .. })));
+        }
+
+        // на линейной шкале те же границы допустимы
+        let options = ChartOptions::default().with_y_scale(AxisScale::Linear).with_y_range(-1.0, 1.0);
+        plot_convergence(&[("Jacobi", &jacobi)], chart_path("linear.svg"), &options).unwrap();
+    }
+}
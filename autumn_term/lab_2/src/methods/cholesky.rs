@@ -0,0 +1,126 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::matrix::SystemMatrix;
+use super::solver::{check_symmetric, FactorizationError, LinearSolver, SolveReport};
+
+// A = L L^T для симметричных положительно определённых матриц
+#[derive(Debug, Clone)]
+pub struct CholeskyFactorization {
+    l: DMatrix<f64>,
+}
+
+impl CholeskyFactorization {
+    pub fn new(matrix: &DMatrix<f64>) -> Result<Self, FactorizationError> {
+        check_symmetric(matrix)?;
+        let n = matrix.nrows();
+        let mut l = DMatrix::zeros(n, n);
+
+        for j in 0..n {
+            let sum: f64 = (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum();
+            let pivot = matrix[(j, j)] - sum;
+            if pivot <= 0.0 {
+                return Err(FactorizationError::NotPositiveDefinite { row: j, pivot });
+            }
+            l[(j, j)] = pivot.sqrt();
+
+            for i in (j + 1)..n {
+                let sum: f64 = (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum();
+                l[(i, j)] = (matrix[(i, j)] - sum) / l[(j, j)];
+            }
+        }
+
+        Ok(CholeskyFactorization { l })
+    }
+
+    pub fn l(&self) -> &DMatrix<f64> {
+        &self.l
+    }
+
+    pub fn solve(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.l.nrows();
+
+        let mut y = b.clone();
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| self.l[(i, k)] * y[k]).sum();
+            y[i] = (y[i] - sum) / self.l[(i, i)];
+        }
+
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| self.l[(k, i)] * y[k]).sum();
+            y[i] = (y[i] - sum) / self.l[(i, i)];
+        }
+
+        y
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.l.diagonal().product().powi(2)
+    }
+}
+
+pub struct Cholesky;
+
+impl LinearSolver for Cholesky {
+    fn name(&self) -> &str {
+        "Cholesky"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        match CholeskyFactorization::new(&matrix.to_dense()) {
+            Ok(factorization) => SolveReport::direct(factorization.solve(f)),
+            Err(error) => SolveReport::failed(f.len(), error),
+        }
+    }
+}
+
+pub fn cholesky_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> Result<DVector<f64>, FactorizationError> {
+    Ok(CholeskyFactorization::new(matrix)?.solve(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_poisson_1d, create_random_spd};
+
+    #[test]
+    fn factors_spd_matrix() {
+        let matrix = create_random_spd(8, 100.0, 7).unwrap();
+        let factorization = CholeskyFactorization::new(&matrix).unwrap();
+        let l = factorization.l();
+
+        assert!((l * l.transpose() - &matrix).amax() < 1e-10);
+        assert!((factorization.determinant() - matrix.determinant()).abs() < 1e-8 * matrix.determinant().abs());
+    }
+
+    #[test]
+    fn solves_known_system() {
+        let matrix = create_poisson_1d(6).to_dense();
+        let x = DVector::from_fn(6, |i, _| i as f64 - 2.0);
+        let solution = cholesky_solve(&matrix, &(&matrix * &x)).unwrap();
+        assert!((solution - x).norm() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_indefinite_and_nonsymmetric_matrices() {
+        let indefinite = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+        assert!(matches!(CholeskyFactorization::new(&indefinite), Err(FactorizationError::NotPositiveDefinite { row: 1, .. })));
+
+        let nonsymmetric = DMatrix::from_row_slice(2, 2, &[2.0, 1.0, 0.0, 2.0]);
+        assert!(matches!(CholeskyFactorization::new(&nonsymmetric), Err(FactorizationError::NotSymmetric { .. })));
+
+        let zero_diagonal = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        assert!(Cholesky.solve(&zero_diagonal, &DVector::from_element(2, 1.0)).error.is_some());
+    }
+
+    #[test]
+    fn empty_system_has_empty_solution() {
+        assert_eq!(cholesky_solve(&DMatrix::zeros(0, 0), &DVector::zeros(0)).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn zero_rhs_gives_zero_solution() {
+        let matrix = create_random_spd(5, 10.0, 1).unwrap();
+        assert_eq!(cholesky_solve(&matrix, &DVector::zeros(5)).unwrap(), DVector::zeros(5));
+    }
+}
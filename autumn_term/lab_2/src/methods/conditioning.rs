@@ -0,0 +1,190 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::lu::{LuFactorization, Pivoting};
+use super::solver::FactorizationError;
+
+pub fn one_norm(matrix: &DMatrix<f64>) -> f64 {
+    matrix.column_iter().map(|column| column.lp_norm(1)).fold(0.0, f64::max)
+}
+
+// оценка ||A^{-1}||_1 методом Хейгера (Hager, 1984) без вычисления обратной матрицы;
+// для вырожденной матрицы - бесконечность
+pub fn hager_inverse_one_norm(factorization: &LuFactorization, n: usize) -> f64 {
+    if factorization.is_singular() {
+        return f64::INFINITY;
+    }
+
+    let mut x = DVector::from_element(n, 1.0 / n as f64);
+    let mut estimate = 0.0;
+
+    for _ in 0..5 {
+        let y = factorization.substitute(&x);
+        let new_estimate = y.lp_norm(1);
+        if new_estimate <= estimate {
+            break;
+        }
+        estimate = new_estimate;
+
+        let xi = y.map(|v| if v >= 0.0 { 1.0 } else { -1.0 });
+        let z = factorization.substitute_transpose(&xi);
+        let (j, z_max) = z.iter().map(|v| v.abs()).enumerate().fold((0, 0.0), |best, c| if c.1 > best.1 { c } else { best });
+        if z_max <= z.dot(&x) {
+            break;
+        }
+
+        x = DVector::zeros(n);
+        x[j] = 1.0;
+    }
+
+    estimate
+}
+
+// оценка cond_2(A) = sigma_max / sigma_min: степенной метод для A^T A и обратный степенной через LU
+pub fn two_norm_condition_number(matrix: &DMatrix<f64>, tolerance: f64, max_iterations: usize) -> Result<f64, FactorizationError> {
+    let n = matrix.ncols();
+    let factorization = LuFactorization::new(matrix, Pivoting::Partial)?;
+    if factorization.is_singular() {
+        return Ok(f64::INFINITY);
+    }
+    let start = DVector::from_fn(n, |i, _| 1.0 + i as f64 / n as f64).normalize();
+
+    let power_iteration = |apply: &dyn Fn(&DVector<f64>) -> DVector<f64>| {
+        let mut v = start.clone();
+        let mut lambda = 0.0;
+        for _ in 0..max_iterations {
+            let w = apply(&v);
+            let new_lambda = v.dot(&w);
+            v = w.normalize();
+            if (new_lambda - lambda).abs() <= tolerance * new_lambda.abs() {
+                return new_lambda;
+            }
+            lambda = new_lambda;
+        }
+        lambda
+    };
+
+    let sigma_max_squared = power_iteration(&|v| matrix.tr_mul(&(matrix * v)));
+    let inverse_sigma_min_squared = power_iteration(&|v| factorization.substitute(&factorization.substitute_transpose(v)));
+
+    Ok((sigma_max_squared * inverse_sigma_min_squared).sqrt())
+}
+
+// степенной метод для спектрального радиуса; шаг делается по B^2, чтобы пары собственных значений
+// +-rho (типичные для матриц итерационных методов) не давали осцилляций
+pub fn estimate_spectral_radius(apply: &dyn Fn(&DVector<f64>) -> DVector<f64>, n: usize, tolerance: f64, max_iterations: usize) -> f64 {
+    let mut v = DVector::from_fn(n, |i, _| 1.0 + (i % 7) as f64 / 7.0).normalize();
+    let mut rho = 0.0;
+
+    for _ in 0..max_iterations {
+        let w = apply(&apply(&v));
+        let w_norm = w.norm();
+        if w_norm == 0.0 {
+            return 0.0;
+        }
+
+        let new_rho = w_norm.sqrt();
+        v = w / w_norm;
+        if (new_rho - rho).abs() <= tolerance * new_rho {
+            return new_rho;
+        }
+        rho = new_rho;
+    }
+
+    rho
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorBounds {
+    pub residual_norm: f64,
+    // нормированная обратная ошибка Риголя-Гаше: ||r|| / (||A|| ||x|| + ||f||)
+    pub backward_error: f64,
+    // оценка сверху для ||x - x*|| / ||x||: ||A^{-1}|| ||r|| / ||x||
+    pub forward_error_bound: f64,
+}
+
+// все нормы - первые (векторная l1 и согласованная с ней матричная)
+pub struct ConditionEstimate {
+    pub matrix_norm: f64,
+    pub inverse_norm: f64,
+    pub condition_number: f64,
+}
+
+impl ConditionEstimate {
+    pub fn new(matrix: &DMatrix<f64>) -> Result<Self, FactorizationError> {
+        let factorization = LuFactorization::new(matrix, Pivoting::Partial)?;
+        let matrix_norm = one_norm(matrix);
+        let inverse_norm = hager_inverse_one_norm(&factorization, matrix.ncols());
+
+        Ok(ConditionEstimate { matrix_norm, inverse_norm, condition_number: matrix_norm * inverse_norm })
+    }
+
+    pub fn error_bounds(&self, matrix: &DMatrix<f64>, x: &DVector<f64>, f: &DVector<f64>) -> ErrorBounds {
+        let residual_norm = (f - matrix * x).lp_norm(1);
+        let x_norm = x.lp_norm(1);
+
+        ErrorBounds {
+            residual_norm,
+            backward_error: residual_norm / (self.matrix_norm * x_norm + f.lp_norm(1)),
+            forward_error_bound: self.inverse_norm * residual_norm / x_norm,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::create_random_spd;
+
+    #[test]
+    fn one_norm_is_max_column_sum() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, -4.0, -2.0, 3.0]);
+        assert_eq!(one_norm(&matrix), 7.0);
+    }
+
+    #[test]
+    fn condition_estimate_is_exact_for_diagonal_matrix() {
+        let matrix = DMatrix::from_diagonal(&DVector::from_vec(vec![1.0, 10.0, 100.0]));
+        let estimate = ConditionEstimate::new(&matrix).unwrap();
+        assert!((estimate.condition_number - 100.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn singular_matrix_has_infinite_condition_number() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        assert_eq!(ConditionEstimate::new(&matrix).unwrap().condition_number, f64::INFINITY);
+        assert_eq!(two_norm_condition_number(&matrix, 1e-12, 100), Ok(f64::INFINITY));
+    }
+
+    #[test]
+    fn non_square_matrix_is_an_error() {
+        let matrix = DMatrix::zeros(2, 3);
+        assert_eq!(ConditionEstimate::new(&matrix).err(), Some(FactorizationError::NotSquare { rows: 2, cols: 3 }));
+        assert_eq!(two_norm_condition_number(&matrix, 1e-12, 100), Err(FactorizationError::NotSquare { rows: 2, cols: 3 }));
+    }
+
+    #[test]
+    fn two_norm_condition_number_of_generated_matrix() {
+        let matrix = create_random_spd(20, 1e3, 5).unwrap();
+        let condition_number = two_norm_condition_number(&matrix, 1e-12, 10000).unwrap();
+        assert!((condition_number / 1e3 - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn spectral_radius_ignores_sign_oscillation() {
+        let b = DMatrix::from_diagonal(&DVector::from_vec(vec![0.5, -0.9, 0.1]));
+        let rho = estimate_spectral_radius(&|v| &b * v, 3, 1e-12, 10000);
+        assert!((rho - 0.9).abs() < 1e-8);
+    }
+
+    #[test]
+    fn exact_solution_has_zero_error_bounds() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[4.0, 1.0, 1.0, 3.0]);
+        let x = DVector::from_vec(vec![1.0, 2.0]);
+        let bounds = ConditionEstimate::new(&matrix).unwrap().error_bounds(&matrix, &x, &(&matrix * &x));
+
+        assert_eq!(bounds.residual_norm, 0.0);
+        assert_eq!(bounds.backward_error, 0.0);
+        assert_eq!(bounds.forward_error_bound, 0.0);
+    }
+}
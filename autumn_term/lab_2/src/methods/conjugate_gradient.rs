@@ -0,0 +1,170 @@
+use nalgebra::DVector;
+
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::preconditioner::Preconditioner;
+use super::solver::{LinearSolver, SolveReport, SolverError, StopReason};
+use super::stopping::StoppingCriterion;
+
+// только для симметричных положительно определённых матриц
+pub struct ConjugateGradient {
+    pub criterion: StoppingCriterion,
+    pub preconditioner: Option<Box<dyn Preconditioner>>,
+}
+
+impl ConjugateGradient {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        ConjugateGradient { criterion: StoppingCriterion::absolute_residual(tolerance, max_iterations), preconditioner: None }
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    // предобуславливатель тоже должен быть симметричным положительно определённым
+    pub fn with_preconditioner(mut self, preconditioner: Box<dyn Preconditioner>) -> Self {
+        self.preconditioner = Some(preconditioner);
+        self
+    }
+}
+
+impl LinearSolver for ConjugateGradient {
+    fn name(&self) -> &str {
+        match self.preconditioner {
+            Some(_) => "Preconditioned conjugate gradient",
+            None => "Conjugate gradient",
+        }
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, f, &mut ())
+    }
+
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let apply_preconditioner = |r: &DVector<f64>| match &self.preconditioner {
+            Some(preconditioner) => preconditioner.apply(r),
+            None => r.clone(),
+        };
+
+        let n = matrix.nrows();
+        let mut x = DVector::zeros(n);
+        let mut r = f - matrix.mul_vec(&x);
+        let mut z = apply_preconditioner(&r);
+        let mut p = z.clone();
+        let mut rz_old = r.dot(&z);
+        let mut monitor = self.criterion.monitor(f).with_observer(observer);
+        let method = match self.preconditioner {
+            Some(_) => "Preconditioned conjugate gradient method",
+            None => "Conjugate gradient method",
+        };
+
+        // при нулевой невязке p = 0 и p^T A p = 0 даже для SPD-матрицы
+        if monitor.initially_converged(r.norm()) {
+            monitor.announce(method, 0, StopReason::Converged);
+            return monitor.finish(x, 0, StopReason::Converged);
+        }
+
+        for iteration in 0..monitor.max_iterations() {
+            let ap = matrix.mul_vec(&p);
+            let p_ap = p.dot(&ap);
+            if p_ap <= 0.0 {
+                return monitor.fail(x, iteration + 1, SolverError::Indefinite { iteration: iteration + 1, curvature: p_ap });
+            }
+
+            let alpha = rz_old / p_ap;
+            x += alpha * &p;
+            r -= alpha * &ap;
+
+            if let Some(stop_reason) = monitor.check(r.norm(), Some(alpha.abs() * p.norm())) {
+                monitor.announce(method, iteration + 1, stop_reason);
+                return monitor.finish(x, iteration + 1, stop_reason);
+            }
+
+            z = apply_preconditioner(&r);
+            let rz_new = r.dot(&z);
+            p = &z + (rz_new / rz_old) * &p;
+            rz_old = rz_new;
+        }
+
+        let max_iterations = monitor.max_iterations();
+        monitor.announce(method, max_iterations, StopReason::MaxIterations);
+        monitor.finish(x, max_iterations, StopReason::MaxIterations)
+    }
+}
+
+pub fn conjugate_gradient_solve(matrix: &dyn SystemMatrix, f: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = ConjugateGradient::new(tolerance, max_iterations).solve(matrix, f);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_poisson_1d, create_poisson_2d, create_vector_f};
+    use crate::methods::preconditioner::IncompleteCholesky;
+
+    #[test]
+    fn solves_poisson_system() {
+        let matrix = create_poisson_2d(8);
+        let f = create_vector_f(64);
+        let report = ConjugateGradient::new(1e-10, 1000).solve(&matrix, &f);
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-9);
+    }
+
+    // в точной арифметике CG сходится не более чем за n шагов
+    #[test]
+    fn converges_in_at_most_n_steps() {
+        let matrix = create_poisson_1d(10);
+        let report = ConjugateGradient::new(1e-10, 1000).solve(&matrix, &create_vector_f(10));
+        assert!(report.iterations <= 10);
+    }
+
+    #[test]
+    fn preconditioning_reduces_iterations() {
+        let matrix = create_poisson_2d(15);
+        let f = create_vector_f(225);
+        let plain = ConjugateGradient::new(1e-10, 1000).solve(&matrix, &f);
+        let preconditioned = ConjugateGradient::new(1e-10, 1000)
+            .with_preconditioner(Box::new(IncompleteCholesky::new(&matrix).unwrap()))
+            .solve(&matrix, &f);
+
+        assert!(preconditioned.converged);
+        assert!(preconditioned.iterations < plain.iterations);
+    }
+
+    #[test]
+    fn zero_rhs_converges_immediately() {
+        let matrix = create_poisson_1d(5);
+        let solver = ConjugateGradient::new(1e-10, 100);
+        let report = solver.solve(&matrix, &DVector::zeros(5));
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert_eq!(report.iterations, 0);
+        assert!(report.error.is_none());
+    }
+
+    #[test]
+    fn indefinite_matrix_is_reported() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 0.0, -1.0]);
+        let report = ConjugateGradient::new(1e-10, 100).solve(&matrix, &DVector::from_vec(vec![0.0, 1.0]));
+
+        match report.error {
+            Some(SolverError::Indefinite { iteration, .. }) => assert_eq!(iteration, report.iterations),
+            error => panic!("expected an indefinite matrix error, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn empty_system_converges_immediately() {
+        let report = ConjugateGradient::new(1e-10, 100).solve(&DMatrix::zeros(0, 0), &DVector::zeros(0));
+
+        assert!(report.converged);
+        assert_eq!(report.iterations, 0);
+        assert!(report.solution.is_empty());
+    }
+}
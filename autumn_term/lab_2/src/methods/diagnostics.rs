@@ -0,0 +1,322 @@
+use nalgebra::DVector;
+
+use std::error::Error;
+use std::fmt;
+
+use super::cholesky::CholeskyFactorization;
+use super::conditioning::estimate_spectral_radius;
+use super::matrix::SystemMatrix;
+use super::ordering::{relaxation_sweep, Sweep};
+use super::parallel::Execution;
+
+// плотная проверка положительной определённости только для не слишком больших матриц
+const DENSE_CHECK_LIMIT: usize = 2000;
+
+// для проверки сходимости достаточно грубой оценки спектрального радиуса
+const SPECTRAL_RADIUS_TOLERANCE: f64 = 1e-6;
+const SPECTRAL_RADIUS_ITERATIONS: usize = 2000;
+
+#[derive(Debug, Clone)]
+pub struct MatrixDiagnostics {
+    pub zero_diagonal_rows: Vec<usize>,
+    pub strictly_diagonally_dominant: bool,
+    pub irreducibly_diagonally_dominant: bool,
+    pub symmetric: bool,
+    // None - не удалось проверить (матрица несимметрична или слишком велика)
+    pub positive_definite: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConvergenceGuarantee {
+    DiagonalDominance,
+    SymmetricPositiveDefinite,
+    SpectralRadius(f64),
+    // достаточные условия не выполнены, а оценка rho >= 1; степенной метод по B^2 не сходится
+    // при комплексных собственных значениях, поэтому это не доказательство расходимости
+    Unverified(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvergenceError {
+    NotSquare { rows: usize, cols: usize },
+    ZeroDiagonal { row: usize },
+    WillNotConverge { method: &'static str, spectral_radius: f64 },
+}
+
+impl fmt::Display for ConvergenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConvergenceError::NotSquare { rows, cols } => write!(f, "matrix is not square ({}x{})", rows, cols),
+            ConvergenceError::ZeroDiagonal { row } => write!(f, "zero diagonal element in row {}", row),
+            ConvergenceError::WillNotConverge { method, spectral_radius } => {
+                write!(f, "{} method will not converge: spectral radius of the iteration matrix is {}", method, spectral_radius)
+            }
+        }
+    }
+}
+
+impl Error for ConvergenceError {}
+
+// матрица неразложима, если её граф сильно связен
+fn is_irreducible(matrix: &dyn SystemMatrix) -> bool {
+    let n = matrix.nrows();
+    let mut forward = vec![Vec::new(); n];
+    let mut backward = vec![Vec::new(); n];
+    for (i, neighbours) in forward.iter_mut().enumerate() {
+        for (j, _) in matrix.row_entries(i) {
+            if i != j {
+                neighbours.push(j);
+                backward[j].push(i);
+            }
+        }
+    }
+
+    let reaches_all = |graph: &[Vec<usize>]| {
+        let mut visited = vec![false; n];
+        let mut stack = vec![0];
+        visited[0] = true;
+        while let Some(i) = stack.pop() {
+            for &j in &graph[i] {
+                if !visited[j] {
+                    visited[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+        visited.iter().all(|&v| v)
+    };
+
+    n == 0 || (reaches_all(&forward) && reaches_all(&backward))
+}
+
+fn is_symmetric(matrix: &dyn SystemMatrix) -> bool {
+    if matrix.nrows() != matrix.ncols() {
+        return false;
+    }
+
+    let mut entries = Vec::new();
+    let mut transposed = Vec::new();
+    for i in 0..matrix.nrows() {
+        for (j, value) in matrix.row_entries(i) {
+            entries.push((i, j, value));
+            transposed.push((j, i, value));
+        }
+    }
+    entries.sort_by_key(|&(i, j, _)| (i, j));
+    transposed.sort_by_key(|&(i, j, _)| (i, j));
+
+    let scale = entries.iter().map(|&(_, _, value)| value.abs()).fold(0.0, f64::max);
+    entries.len() == transposed.len()
+        && entries.iter().zip(&transposed).all(|(a, b)| (a.0, a.1) == (b.0, b.1) && (a.2 - b.2).abs() <= 1e-12 * scale)
+}
+
+fn check_square(matrix: &dyn SystemMatrix) -> Result<(), ConvergenceError> {
+    if matrix.nrows() != matrix.ncols() {
+        return Err(ConvergenceError::NotSquare { rows: matrix.nrows(), cols: matrix.ncols() });
+    }
+    Ok(())
+}
+
+// полный разбор для отчёта; при положительной диагонали без диагонального преобладания
+// симметричная матрица до DENSE_CHECK_LIMIT раскладывается плотным Холецким - это O(n^3)
+pub fn diagnose(matrix: &dyn SystemMatrix) -> Result<MatrixDiagnostics, ConvergenceError> {
+    check_square(matrix)?;
+    let n = matrix.nrows();
+    let diagonal = matrix.diagonal();
+    let zero_diagonal_rows: Vec<usize> = (0..n).filter(|&i| diagonal[i] == 0.0).collect();
+
+    let mut weakly_dominant = true;
+    let mut strictly_dominant = true;
+    let mut any_strict_row = false;
+    for i in 0..n {
+        let off_diagonal: f64 = matrix.row_entries(i).iter().filter(|&&(j, _)| j != i).map(|&(_, value)| value.abs()).sum();
+        let d = diagonal[i].abs();
+        weakly_dominant &= d >= off_diagonal;
+        strictly_dominant &= d > off_diagonal;
+        any_strict_row |= d > off_diagonal;
+    }
+    let irreducibly_dominant = weakly_dominant && any_strict_row && is_irreducible(matrix);
+
+    let symmetric = is_symmetric(matrix);
+    let positive_definite = if !symmetric {
+        None
+    } else if diagonal.iter().all(|&d| d > 0.0) && (strictly_dominant || irreducibly_dominant) {
+        // по теореме Гершгорина
+        Some(true)
+    } else if n <= DENSE_CHECK_LIMIT {
+        Some(CholeskyFactorization::new(&matrix.to_dense()).is_ok())
+    } else {
+        None
+    };
+
+    Ok(MatrixDiagnostics {
+        zero_diagonal_rows,
+        strictly_diagonally_dominant: strictly_dominant,
+        irreducibly_diagonally_dominant: irreducibly_dominant,
+        symmetric,
+        positive_definite,
+    })
+}
+
+// B_J = I - D^{-1} A
+pub fn jacobi_iteration_spectral_radius(matrix: &dyn SystemMatrix, tolerance: f64, max_iterations: usize) -> f64 {
+    let diagonal = matrix.diagonal();
+    let apply = |v: &DVector<f64>| v - matrix.mul_vec(v).component_div(&diagonal);
+    estimate_spectral_radius(&apply, matrix.nrows(), tolerance, max_iterations)
+}
+
+// B_S = -(D + L)^{-1} U, применяется одним прямым ходом
+pub fn seidel_iteration_spectral_radius(matrix: &dyn SystemMatrix, tolerance: f64, max_iterations: usize) -> f64 {
+    relaxation_iteration_spectral_radius(matrix, 1.0, None, Sweep::Forward, tolerance, max_iterations)
+}
+
+// матрица перехода релаксации с заданными порядком и проходом: один шаг с нулевой правой частью
+pub fn relaxation_iteration_spectral_radius(matrix: &dyn SystemMatrix, omega: f64, colors: Option<&[Vec<usize>]>, sweep: Sweep,
+    tolerance: f64, max_iterations: usize) -> f64 {
+    let zero = DVector::zeros(matrix.nrows());
+    let apply = |v: &DVector<f64>| {
+        let mut w = v.clone();
+        relaxation_sweep(matrix, &zero, &mut w, omega, colors, sweep, Execution::Serial);
+        w
+    };
+    estimate_spectral_radius(&apply, matrix.nrows(), tolerance, max_iterations)
+}
+
+fn check_diagonal(diagnostics: &MatrixDiagnostics) -> Result<(), ConvergenceError> {
+    match diagnostics.zero_diagonal_rows.first() {
+        Some(&row) => Err(ConvergenceError::ZeroDiagonal { row }),
+        None => Ok(()),
+    }
+}
+
+fn spectral_radius_guarantee(spectral_radius: f64) -> ConvergenceGuarantee {
+    if spectral_radius < 1.0 {
+        ConvergenceGuarantee::SpectralRadius(spectral_radius)
+    } else {
+        ConvergenceGuarantee::Unverified(spectral_radius)
+    }
+}
+
+// проверки перед итерациями за O(n): только условия, без которых шаг не определён
+// или расходимость доказана (теорема Кахана); достаточные условия - в check_*_convergence
+pub fn jacobi_precheck(matrix: &dyn SystemMatrix) -> Result<(), ConvergenceError> {
+    check_square(matrix)?;
+    match matrix.diagonal().iter().position(|&value| value == 0.0) {
+        Some(row) => Err(ConvergenceError::ZeroDiagonal { row }),
+        None => Ok(()),
+    }
+}
+
+pub fn relaxation_precheck(matrix: &dyn SystemMatrix, omega: f64) -> Result<(), ConvergenceError> {
+    jacobi_precheck(matrix)?;
+
+    // по теореме Кахана rho(B_omega) >= |omega - 1|
+    if omega <= 0.0 || omega >= 2.0 {
+        return Err(ConvergenceError::WillNotConverge { method: "Upper relaxation", spectral_radius: (omega - 1.0).abs() });
+    }
+
+    Ok(())
+}
+
+pub fn check_jacobi_convergence(matrix: &dyn SystemMatrix) -> Result<ConvergenceGuarantee, ConvergenceError> {
+    let diagnostics = diagnose(matrix)?;
+    check_diagonal(&diagnostics)?;
+
+    if diagnostics.strictly_diagonally_dominant || diagnostics.irreducibly_diagonally_dominant {
+        return Ok(ConvergenceGuarantee::DiagonalDominance);
+    }
+
+    Ok(spectral_radius_guarantee(jacobi_iteration_spectral_radius(matrix, SPECTRAL_RADIUS_TOLERANCE, SPECTRAL_RADIUS_ITERATIONS)))
+}
+
+// проверка для естественного порядка; для других порядков и SSOR - check_relaxation_convergence
+pub fn check_seidel_convergence(matrix: &dyn SystemMatrix) -> Result<ConvergenceGuarantee, ConvergenceError> {
+    check_relaxation_convergence(matrix, 1.0, None, Sweep::Forward)
+}
+
+// диагональное преобладание (при omega <= 1) и симметричная положительная определённость
+// (теорема Островского-Райха) не зависят от порядка обхода; иначе спектральный радиус
+// оценивается для выбранных раскраски и прохода
+pub fn check_relaxation_convergence(matrix: &dyn SystemMatrix, omega: f64, colors: Option<&[Vec<usize>]>, sweep: Sweep)
+    -> Result<ConvergenceGuarantee, ConvergenceError> {
+    relaxation_precheck(matrix, omega)?;
+    let diagnostics = diagnose(matrix)?;
+
+    if omega <= 1.0 && (diagnostics.strictly_diagonally_dominant || diagnostics.irreducibly_diagonally_dominant) {
+        return Ok(ConvergenceGuarantee::DiagonalDominance);
+    }
+    if diagnostics.positive_definite == Some(true) {
+        return Ok(ConvergenceGuarantee::SymmetricPositiveDefinite);
+    }
+
+    let spectral_radius = relaxation_iteration_spectral_radius(matrix, omega, colors, sweep, SPECTRAL_RADIUS_TOLERANCE, SPECTRAL_RADIUS_ITERATIONS);
+    Ok(spectral_radius_guarantee(spectral_radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_poisson_1d};
+    use crate::methods::ordering::red_black_coloring;
+
+    #[test]
+    fn diagnoses_poisson_matrix() {
+        let diagnostics = diagnose(&create_poisson_1d(10)).unwrap();
+        assert!(diagnostics.zero_diagonal_rows.is_empty());
+        assert!(!diagnostics.strictly_diagonally_dominant);
+        assert!(diagnostics.irreducibly_diagonally_dominant);
+        assert!(diagnostics.symmetric);
+        assert_eq!(diagnostics.positive_definite, Some(true));
+    }
+
+    #[test]
+    fn zero_diagonal_fails_every_check() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let expected = Err(ConvergenceError::ZeroDiagonal { row: 0 });
+        assert_eq!(check_jacobi_convergence(&matrix), expected);
+        assert_eq!(check_seidel_convergence(&matrix), expected);
+        assert_eq!(check_relaxation_convergence(&matrix, 1.5, None, Sweep::Forward), expected);
+        assert_eq!(jacobi_precheck(&matrix), Err(ConvergenceError::ZeroDiagonal { row: 0 }));
+    }
+
+    #[test]
+    fn relaxation_needs_omega_between_zero_and_two() {
+        let matrix = create_custom_sparse_matrix(10, 10.0);
+        assert!(check_relaxation_convergence(&matrix, 1.2, None, Sweep::Forward).is_ok());
+        assert!(matches!(check_relaxation_convergence(&matrix, 2.5, None, Sweep::Forward),
+            Err(ConvergenceError::WillNotConverge { spectral_radius, .. }) if spectral_radius == 1.5));
+    }
+
+    // A = [[1, 2], [2, 1]]: rho(B_J) = 2, rho(B_S) = 4; оценка - не доказательство, поэтому не ошибка
+    #[test]
+    fn large_spectral_radius_is_unverified() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+        assert!(matches!(check_jacobi_convergence(&matrix), Ok(ConvergenceGuarantee::Unverified(rho)) if (rho - 2.0).abs() < 1e-4));
+        assert!(matches!(check_seidel_convergence(&matrix), Ok(ConvergenceGuarantee::Unverified(rho)) if (rho - 4.0).abs() < 1e-4));
+        assert!(relaxation_precheck(&matrix, 1.0).is_ok());
+    }
+
+    #[test]
+    fn non_square_matrix_is_an_error() {
+        let matrix = DMatrix::from_element(2, 3, 1.0);
+        let expected = ConvergenceError::NotSquare { rows: 2, cols: 3 };
+        assert_eq!(diagnose(&matrix).unwrap_err(), expected);
+        assert_eq!(jacobi_precheck(&matrix), Err(expected.clone()));
+        assert_eq!(check_relaxation_convergence(&matrix, 1.2, None, Sweep::Forward), Err(expected));
+    }
+
+    // для согласованно упорядоченной матрицы rho(B_S) = rho(B_J)^2 в обоих порядках
+    #[test]
+    fn spectral_radius_follows_the_ordering() {
+        let matrix = create_poisson_1d(20);
+        let colors = red_black_coloring(&matrix).unwrap();
+        let jacobi = jacobi_iteration_spectral_radius(&matrix, 1e-12, 100000);
+        let natural = seidel_iteration_spectral_radius(&matrix, 1e-12, 100000);
+        let red_black = relaxation_iteration_spectral_radius(&matrix, 1.0, Some(&colors), Sweep::Forward, 1e-12, 100000);
+
+        assert!((natural - jacobi * jacobi).abs() < 1e-6);
+        assert!((red_black - jacobi * jacobi).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,471 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::lu::{LuFactorization, Pivoting};
+use super::matrix::SystemMatrix;
+use super::solver::{check_square, FactorizationError};
+
+// одна собственная пара; в residuals - ||A v - lambda v|| на каждой итерации, как невязки у решателей
+#[derive(Debug, Clone)]
+pub struct EigenReport {
+    pub eigenvalue: f64,
+    pub eigenvector: DVector<f64>,
+    pub residuals: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+// весь спектр: пары (Re, Im); в residuals - наименьший поддиагональный элемент активного блока
+#[derive(Debug, Clone)]
+pub struct SpectrumReport {
+    pub eigenvalues: Vec<(f64, f64)>,
+    pub residuals: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+// значения Ритца по возрастанию; в residuals - оценка |beta_k y_k| для старшего значения Ритца
+#[derive(Debug, Clone)]
+pub struct LanczosReport {
+    pub ritz_values: Vec<f64>,
+    pub residuals: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+fn start_vector(n: usize) -> DVector<f64> {
+    DVector::from_fn(n, |i, _| 1.0 + i as f64 / n as f64).normalize()
+}
+
+fn eigen_residual(matrix: &dyn SystemMatrix, lambda: f64, v: &DVector<f64>) -> f64 {
+    (matrix.mul_vec(v) - lambda * v).norm()
+}
+
+// наибольшее по модулю собственное значение
+pub fn power_iteration(matrix: &dyn SystemMatrix, tolerance: f64, max_iterations: usize) -> EigenReport {
+    let mut v = start_vector(matrix.ncols());
+    let mut lambda = 0.0;
+    let mut residuals = Vec::new();
+
+    for iteration in 0..max_iterations {
+        let w = matrix.mul_vec(&v);
+        lambda = v.dot(&w);
+        let w_norm = w.norm();
+        if w_norm == 0.0 {
+            return EigenReport { eigenvalue: 0.0, eigenvector: v, residuals, iterations: iteration + 1, converged: true };
+        }
+        v = w / w_norm;
+
+        let residual = eigen_residual(matrix, lambda, &v);
+        residuals.push(residual);
+        if residual < tolerance * lambda.abs().max(1.0) {
+            return EigenReport { eigenvalue: lambda, eigenvector: v, residuals, iterations: iteration + 1, converged: true };
+        }
+    }
+
+    EigenReport { eigenvalue: lambda, eigenvector: v, residuals, iterations: max_iterations, converged: false }
+}
+
+fn shifted_factorization(matrix: &DMatrix<f64>, shift: f64) -> Result<LuFactorization, FactorizationError> {
+    check_square(matrix)?;
+    let n = matrix.nrows();
+    let mut factorization = LuFactorization::new(&(matrix - shift * DMatrix::identity(n, n)), Pivoting::Partial)?;
+    // сдвиг попал точно в собственное значение - чуть отодвигаем его
+    let mut perturbed = shift;
+    while factorization.is_singular() {
+        perturbed += f64::EPSILON.sqrt() * shift.abs().max(1.0);
+        factorization = LuFactorization::new(&(matrix - perturbed * DMatrix::identity(n, n)), Pivoting::Partial)?;
+    }
+    Ok(factorization)
+}
+
+// собственное значение, ближайшее к shift: степенной метод для (A - shift I)^{-1}
+pub fn inverse_iteration(matrix: &DMatrix<f64>, shift: f64, tolerance: f64, max_iterations: usize) -> Result<EigenReport, FactorizationError> {
+    let factorization = shifted_factorization(matrix, shift)?;
+    let mut v = start_vector(matrix.ncols());
+    let mut lambda = shift;
+    let mut residuals = Vec::new();
+
+    for iteration in 0..max_iterations {
+        v = factorization.substitute(&v).normalize();
+        lambda = v.dot(&(matrix * &v));
+
+        let residual = eigen_residual(matrix, lambda, &v);
+        residuals.push(residual);
+        if residual < tolerance * lambda.abs().max(1.0) {
+            return Ok(EigenReport { eigenvalue: lambda, eigenvector: v, residuals, iterations: iteration + 1, converged: true });
+        }
+    }
+
+    Ok(EigenReport { eigenvalue: lambda, eigenvector: v, residuals, iterations: max_iterations, converged: false })
+}
+
+// сдвиг - отношение Рэлея на каждой итерации; для симметричных матриц сходимость кубическая
+pub fn rayleigh_quotient_iteration(matrix: &DMatrix<f64>, initial: &DVector<f64>, tolerance: f64, max_iterations: usize) -> Result<EigenReport, FactorizationError> {
+    check_square(matrix)?;
+    let mut v = initial.normalize();
+    let mut lambda = v.dot(&(matrix * &v));
+    let mut residuals = Vec::new();
+
+    for iteration in 0..max_iterations {
+        v = shifted_factorization(matrix, lambda)?.substitute(&v).normalize();
+        lambda = v.dot(&(matrix * &v));
+
+        let residual = eigen_residual(matrix, lambda, &v);
+        residuals.push(residual);
+        if residual < tolerance * lambda.abs().max(1.0) {
+            return Ok(EigenReport { eigenvalue: lambda, eigenvector: v, residuals, iterations: iteration + 1, converged: true });
+        }
+    }
+
+    Ok(EigenReport { eigenvalue: lambda, eigenvector: v, residuals, iterations: max_iterations, converged: false })
+}
+
+// Ланцош с полной реортогонализацией для симметричных (в том числе разреженных) матриц;
+// значения Ритца - собственные значения трёхдиагональной T_k, считаются вращениями Якоби
+pub fn lanczos(matrix: &dyn SystemMatrix, steps: usize, tolerance: f64) -> LanczosReport {
+    let n = matrix.ncols();
+    let steps = steps.min(n);
+    let mut basis: Vec<DVector<f64>> = vec![start_vector(n)];
+    let mut alphas: Vec<f64> = Vec::new();
+    let mut betas: Vec<f64> = Vec::new();
+    let mut residuals = Vec::new();
+    let mut ritz_values = Vec::new();
+
+    for k in 0..steps {
+        let mut w = matrix.mul_vec(&basis[k]);
+        let alpha = w.dot(&basis[k]);
+        alphas.push(alpha);
+        // Грам-Шмидт дважды: после одного прохода при малом beta ортогональность теряется
+        for _ in 0..2 {
+            for v in &basis {
+                let projection = w.dot(v);
+                w -= projection * v;
+            }
+        }
+        let beta = w.norm();
+
+        let t = DMatrix::from_fn(k + 1, k + 1, |i, j| {
+            if i == j { alphas[i] } else if i == j + 1 { betas[j] } else if j == i + 1 { betas[i] } else { 0.0 }
+        });
+        let (values, vectors) = symmetric_eigen(&t);
+        let largest = values.len() - 1;
+        // ||A V y - theta V y|| = |beta_k| |y_k| для пары Ритца (theta, V y)
+        let residual = beta * vectors[(k, largest)].abs();
+        residuals.push(residual);
+        ritz_values = values;
+
+        // beta на уровне округлений - подпространство Крылова инвариантно, продолжать нельзя
+        let invariant = beta <= f64::EPSILON * ritz_values[largest].abs().max(1.0);
+        if residual < tolerance * ritz_values[largest].abs().max(1.0) || invariant {
+            return LanczosReport { ritz_values, residuals, iterations: k + 1, converged: true };
+        }
+
+        betas.push(beta);
+        basis.push(w / beta);
+    }
+
+    LanczosReport { ritz_values, residuals, iterations: steps, converged: false }
+}
+
+// собственные пары маленькой симметричной матрицы методом вращений Якоби, по возрастанию
+fn symmetric_eigen(t: &DMatrix<f64>) -> (Vec<f64>, DMatrix<f64>) {
+    let n = t.nrows();
+    let mut a = t.clone();
+    let mut v = DMatrix::identity(n, n);
+
+    for _ in 0..100 {
+        let off: f64 = (0..n).flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j))).map(|(i, j)| a[(i, j)].powi(2)).sum();
+        if off.sqrt() <= f64::EPSILON * a.norm() {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[(p, q)] == 0.0 {
+                    continue;
+                }
+                let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                    a[(k, p)] = c * akp - s * akq;
+                    a[(k, q)] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                    a[(p, k)] = c * apk - s * aqk;
+                    a[(q, k)] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                    v[(k, p)] = c * vkp - s * vkq;
+                    v[(k, q)] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[(i, i)].total_cmp(&a[(j, j)]));
+    let values = order.iter().map(|&i| a[(i, i)]).collect();
+    let vectors = DMatrix::from_fn(n, n, |i, j| v[(i, order[j])]);
+    (values, vectors)
+}
+
+// единичный вектор отражения Хаусхолдера, переводящего x в кратное e_1; None, если x = 0
+fn householder_vector(x: &DVector<f64>) -> Option<DVector<f64>> {
+    let norm = x.norm();
+    if norm == 0.0 {
+        return None;
+    }
+    let mut v = x.clone();
+    v[0] += if x[0] >= 0.0 { norm } else { -norm };
+    let v_norm = v.norm();
+    Some(v / v_norm)
+}
+
+// H = Q^T A Q - верхняя форма Хессенберга, отражения Хаусхолдера
+pub fn hessenberg(matrix: &DMatrix<f64>) -> DMatrix<f64> {
+    let n = matrix.nrows();
+    let mut h = matrix.clone();
+
+    for k in 0..n.saturating_sub(2) {
+        let x = h.view((k + 1, k), (n - k - 1, 1)).column(0).into_owned();
+        if let Some(v) = householder_vector(&x) {
+            let mut rows = h.view_mut((k + 1, k), (n - k - 1, n - k));
+            let projection = v.transpose() * &rows;
+            rows -= 2.0 * &v * projection;
+
+            let mut columns = h.view_mut((0, k + 1), (n, n - k - 1));
+            let projection = &columns * &v;
+            columns -= 2.0 * projection * v.transpose();
+        }
+        for i in (k + 2)..n {
+            h[(i, k)] = 0.0;
+        }
+    }
+
+    h
+}
+
+// собственные значения блока [[a, b], [c, d]]
+fn two_by_two_eigenvalues(a: f64, b: f64, c: f64, d: f64) -> [(f64, f64); 2] {
+    let mean = (a + d) / 2.0;
+    let discriminant = ((a - d) / 2.0).powi(2) + b * c;
+    if discriminant >= 0.0 {
+        let root = discriminant.sqrt();
+        [(mean + root, 0.0), (mean - root, 0.0)]
+    } else {
+        let root = (-discriminant).sqrt();
+        [(mean, root), (mean, -root)]
+    }
+}
+
+// отражение (I - 2 v v^T) применяется к строкам rows и столбцам columns
+fn reflect_rows(h: &mut DMatrix<f64>, v: &DVector<f64>, first_row: usize, columns: std::ops::RangeInclusive<usize>) {
+    for j in columns {
+        let projection: f64 = (0..v.len()).map(|i| v[i] * h[(first_row + i, j)]).sum();
+        for i in 0..v.len() {
+            h[(first_row + i, j)] -= 2.0 * v[i] * projection;
+        }
+    }
+}
+
+fn reflect_columns(h: &mut DMatrix<f64>, v: &DVector<f64>, first_column: usize, rows: std::ops::RangeInclusive<usize>) {
+    for i in rows {
+        let projection: f64 = (0..v.len()).map(|j| h[(i, first_column + j)] * v[j]).sum();
+        for j in 0..v.len() {
+            h[(i, first_column + j)] -= 2.0 * projection * v[j];
+        }
+    }
+}
+
+// неявный шаг Фрэнсиса с двойным сдвигом на активном блоке low..=high (Golub, Van Loan, алгоритм 7.5.1)
+fn francis_step(h: &mut DMatrix<f64>, low: usize, high: usize) {
+    let size = high - low + 1;
+    let (a, b) = (high - 1, high);
+    let s = h[(a, a)] + h[(b, b)];
+    let t = h[(a, a)] * h[(b, b)] - h[(a, b)] * h[(b, a)];
+
+    let mut x = h[(low, low)] * h[(low, low)] + h[(low, low + 1)] * h[(low + 1, low)] - s * h[(low, low)] + t;
+    let mut y = h[(low + 1, low)] * (h[(low, low)] + h[(low + 1, low + 1)] - s);
+    let mut z = h[(low + 1, low)] * h[(low + 2, low + 1)];
+
+    for k in 0..size - 2 {
+        if let Some(v) = householder_vector(&DVector::from_vec(vec![x, y, z])) {
+            let first_column = low + k.saturating_sub(1);
+            reflect_rows(h, &v, low + k, first_column..=high);
+            let last_row = low + (k + 3).min(size - 1);
+            reflect_columns(h, &v, low + k, low..=last_row);
+        }
+
+        x = h[(low + k + 1, low + k)];
+        y = h[(low + k + 2, low + k)];
+        if k + 3 < size {
+            z = h[(low + k + 3, low + k)];
+        }
+    }
+
+    if let Some(v) = householder_vector(&DVector::from_vec(vec![x, y])) {
+        reflect_rows(h, &v, high - 1, (high - 2)..=high);
+        reflect_columns(h, &v, high - 1, low..=high);
+    }
+}
+
+// QR-алгоритм со сдвигами: приведение к форме Хессенберга, двойные сдвиги Фрэнсиса и исчерпывание
+pub fn qr_algorithm(matrix: &DMatrix<f64>, tolerance: f64, max_iterations: usize) -> SpectrumReport {
+    assert!(matrix.is_square(), "QR algorithm needs a square matrix");
+    let n = matrix.nrows();
+    let mut h = hessenberg(matrix);
+    let mut eigenvalues = Vec::with_capacity(n);
+    let mut residuals = Vec::new();
+    let mut iterations = 0;
+    let mut high = n as isize - 1;
+
+    while high >= 0 {
+        let hi = high as usize;
+        if hi == 0 {
+            eigenvalues.push((h[(0, 0)], 0.0));
+            break;
+        }
+
+        // ищем пренебрежимо малый поддиагональный элемент снизу вверх
+        let mut low = 0;
+        for k in (1..=hi).rev() {
+            let scale = h[(k, k)].abs() + h[(k - 1, k - 1)].abs();
+            if h[(k, k - 1)].abs() <= tolerance * scale || h[(k, k - 1)].abs() < f64::MIN_POSITIVE {
+                h[(k, k - 1)] = 0.0;
+                low = k;
+                break;
+            }
+        }
+
+        if low == hi {
+            eigenvalues.push((h[(hi, hi)], 0.0));
+            high -= 1;
+        } else if low + 1 == hi {
+            eigenvalues.extend(two_by_two_eigenvalues(h[(hi - 1, hi - 1)], h[(hi - 1, hi)], h[(hi, hi - 1)], h[(hi, hi)]));
+            high -= 2;
+        } else {
+            if iterations == max_iterations {
+                return SpectrumReport { eigenvalues, residuals, iterations, converged: false };
+            }
+            francis_step(&mut h, low, hi);
+            iterations += 1;
+            residuals.push(h[(hi, hi - 1)].abs().min(h[(hi - 1, hi - 2)].abs()));
+        }
+    }
+
+    eigenvalues.reverse();
+    SpectrumReport { eigenvalues, residuals, iterations, converged: true }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_poisson_1d, create_random_spd, create_tridiagonal_toeplitz, tridiagonal_toeplitz_eigenvalues};
+
+    fn sorted_eigenvalues(sub: f64, diagonal: f64, sup: f64, n: usize) -> Vec<f64> {
+        let mut eigenvalues = tridiagonal_toeplitz_eigenvalues(n, sub, diagonal, sup).unwrap();
+        eigenvalues.sort_by(f64::total_cmp);
+        eigenvalues
+    }
+
+    #[test]
+    fn power_iteration_finds_largest_eigenvalue() {
+        let expected = sorted_eigenvalues(-1.0, 2.0, -1.0, 10);
+        let report = power_iteration(&create_poisson_1d(10), 1e-8, 10000);
+
+        assert!(report.converged);
+        assert!((report.eigenvalue - expected[9]).abs() < 1e-8);
+    }
+
+    #[test]
+    fn inverse_iteration_finds_eigenvalue_nearest_to_shift() {
+        let expected = sorted_eigenvalues(-1.0, 2.0, -1.0, 10);
+        let matrix = create_poisson_1d(10).to_dense();
+
+        let smallest = inverse_iteration(&matrix, 0.0, 1e-10, 1000).unwrap();
+        assert!((smallest.eigenvalue - expected[0]).abs() < 1e-10);
+
+        // сдвиг ровно в собственное значение не должен давать вырожденную систему
+        let exact_shift = inverse_iteration(&matrix, expected[4], 1e-10, 1000).unwrap();
+        assert!(exact_shift.converged);
+        assert!((exact_shift.eigenvalue - expected[4]).abs() < 1e-8);
+    }
+
+    #[test]
+    fn rayleigh_quotient_iteration_converges_in_few_steps() {
+        let matrix = create_random_spd(15, 100.0, 3).unwrap();
+        let report = rayleigh_quotient_iteration(&matrix, &DVector::from_element(15, 1.0), 1e-12, 20).unwrap();
+
+        assert!(report.converged);
+        assert!(report.iterations <= 6);
+        assert!((&matrix * &report.eigenvector - report.eigenvalue * &report.eigenvector).norm() < 1e-10);
+    }
+
+    #[test]
+    fn inverse_iterations_reject_non_square_matrix() {
+        let matrix = DMatrix::zeros(2, 3);
+        assert_eq!(inverse_iteration(&matrix, 0.0, 1e-10, 10).err(), Some(FactorizationError::NotSquare { rows: 2, cols: 3 }));
+        assert_eq!(rayleigh_quotient_iteration(&matrix, &DVector::from_element(3, 1.0), 1e-10, 10).err(),
+            Some(FactorizationError::NotSquare { rows: 2, cols: 3 }));
+    }
+
+    #[test]
+    fn lanczos_recovers_extreme_eigenvalues() {
+        let expected = sorted_eigenvalues(-1.0, 3.0, -1.0, 20);
+        let report = lanczos(&create_tridiagonal_toeplitz(20, -1.0, 3.0, -1.0), 20, 1e-10);
+
+        assert!(report.converged);
+        assert!((report.ritz_values.last().unwrap() - expected[19]).abs() < 1e-8);
+    }
+
+    #[test]
+    fn qr_algorithm_finds_real_and_complex_eigenvalues() {
+        // собственные значения 3 и 1 +- 2i
+        let matrix = DMatrix::from_row_slice(3, 3, &[1.0, -2.0, 0.0, 2.0, 1.0, 0.0, 0.0, 0.0, 3.0]);
+        let mut eigenvalues = qr_algorithm(&matrix, 1e-14, 1000).eigenvalues;
+        eigenvalues.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+        let expected = [(1.0, -2.0), (1.0, 2.0), (3.0, 0.0)];
+        for (value, exact) in eigenvalues.iter().zip(&expected) {
+            assert!((value.0 - exact.0).abs() < 1e-12 && (value.1 - exact.1).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn qr_algorithm_matches_symmetric_spectrum() {
+        let expected = sorted_eigenvalues(-1.0, 2.0, -1.0, 12);
+        let report = qr_algorithm(&create_poisson_1d(12).to_dense(), 1e-14, 1000);
+        let mut eigenvalues: Vec<f64> = report.eigenvalues.iter().map(|&(re, im)| { assert_eq!(im, 0.0); re }).collect();
+        eigenvalues.sort_by(f64::total_cmp);
+
+        assert!(report.converged);
+        for (value, exact) in eigenvalues.iter().zip(&expected) {
+            assert!((value - exact).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn hessenberg_preserves_spectrum_and_shape() {
+        let matrix = create_random_spd(6, 10.0, 1).unwrap();
+        let h = hessenberg(&matrix);
+
+        assert!((0..6).all(|j| ((j + 2)..6).all(|i| h[(i, j)] == 0.0)));
+        assert!((h.trace() - matrix.trace()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn zero_and_empty_matrices() {
+        let report = power_iteration(&DMatrix::<f64>::zeros(3, 3), 1e-10, 10);
+        assert!(report.converged);
+        assert_eq!(report.eigenvalue, 0.0);
+
+        assert!(qr_algorithm(&DMatrix::zeros(0, 0), 1e-12, 10).eigenvalues.is_empty());
+        assert!(lanczos(&DMatrix::<f64>::zeros(0, 0), 5, 1e-10).ritz_values.is_empty());
+    }
+}
@@ -1,6 +1,24 @@
 use nalgebra::{DMatrix, DVector};
 
-pub fn gauss_pivot_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> DVector<f64> {
+use super::matrix::SystemMatrix;
+use super::solver::{LinearSolver, SolveReport, SolverError};
+
+pub struct GaussPivot;
+
+impl LinearSolver for GaussPivot {
+    fn name(&self) -> &str {
+        "Gauss"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        match gauss_pivot_solve(&matrix.to_dense(), f) {
+            Ok(solution) => SolveReport::direct(solution),
+            Err(error) => SolveReport::failed(f.len(), error),
+        }
+    }
+}
+
+pub fn gauss_pivot_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> Result<DVector<f64>, SolverError> {
     let n = matrix.nrows();
     let mut a = matrix.clone();
     let mut b = b.clone();
@@ -13,6 +31,11 @@ pub fn gauss_pivot_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> DVector<f64
             b.swap_rows(i, max_row);
         }
 
+        // весь остаток столбца нулевой: матрица вырождена, дальше были бы NaN и inf
+        if a[(i, i)] == 0.0 {
+            return Err(SolverError::ZeroPivot { row: i });
+        }
+
         for j in i + 1..n {
             let factor = a[(j, i)] / a[(i, i)];
             for k in i..n {
@@ -31,6 +54,39 @@ pub fn gauss_pivot_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> DVector<f64
         x[i] = (b[i] - sum) / a[(i, i)];
     }
 
-    x
+    Ok(x)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_custom_matrix, create_vector_f};
+
+    #[test]
+    fn solves_banded_system() {
+        let matrix = create_custom_matrix(30, 10.0);
+        let f = create_vector_f(30);
+        assert!((&matrix * gauss_pivot_solve(&matrix, &f).unwrap() - &f).norm() < 1e-10);
+    }
+
+    #[test]
+    fn zero_diagonal_needs_row_swap() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(gauss_pivot_solve(&matrix, &DVector::from_vec(vec![2.0, 3.0])).unwrap(), DVector::from_vec(vec![3.0, 2.0]));
+    }
+
+    #[test]
+    fn singular_matrix_has_zero_pivot() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        assert!(matches!(gauss_pivot_solve(&matrix, &DVector::zeros(2)), Err(SolverError::ZeroPivot { row: 1 })));
+    }
+
+    #[test]
+    fn zero_rhs_and_empty_system() {
+        let matrix = create_custom_matrix(5, 10.0);
+        assert_eq!(gauss_pivot_solve(&matrix, &DVector::zeros(5)).unwrap(), DVector::zeros(5));
+        assert!(gauss_pivot_solve(&DMatrix::zeros(0, 0), &DVector::zeros(0)).unwrap().is_empty());
+    }
+}
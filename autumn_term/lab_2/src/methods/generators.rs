@@ -0,0 +1,315 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::matrix::SystemMatrix;
+use super::qr::QrFactorization;
+use super::solver::SolverError;
+use super::sparse::CsrMatrix;
+use super::tridiagonal::BlockTridiagonal;
+
+// т.к. решал пункт д)
+pub fn create_custom_matrix(n: usize, a: f64) -> DMatrix<f64> {
+    let mut matrix = DMatrix::zeros(n, n);
+
+    for i in 0..n {
+        for j in 0..n {
+            if i == j {
+                matrix[(i, j)] = a;
+            } else if (i as isize - j as isize).abs() <= 2 {
+                matrix[(i, j)] = 1.0;
+            }
+        }
+    }
+
+    matrix
+}
+
+// та же матрица пункта д), но в CSR - для больших n
+pub fn create_custom_sparse_matrix(n: usize, a: f64) -> CsrMatrix {
+    CsrMatrix::from_bands(n, &[(-2, 1.0), (-1, 1.0), (0, a), (1, 1.0), (2, 1.0)])
+}
+
+// т.к. решал пункт д)
+pub fn create_vector_f(n: usize) -> DVector<f64> {
+    let mut f = DVector::zeros(n);
+
+    for i in 0..n {
+        f[i] = (i + 1) as f64;
+    }
+
+    f
+}
+
+// H_ij = 1 / (i + j + 1), cond растёт как e^{3.5 n}
+pub fn create_hilbert_matrix(n: usize) -> DMatrix<f64> {
+    DMatrix::from_fn(n, n, |i, j| 1.0 / (i + j + 1) as f64)
+}
+
+// V_ij = x_i^j
+pub fn create_vandermonde_matrix(nodes: &[f64]) -> DMatrix<f64> {
+    let n = nodes.len();
+    DMatrix::from_fn(n, n, |i, j| nodes[i].powi(j as i32))
+}
+
+// равномерные узлы на [0, 1]
+pub fn uniform_nodes(n: usize) -> Vec<f64> {
+    (0..n).map(|i| if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 }).collect()
+}
+
+// tridiag(sub, diagonal, sup) с постоянными диагоналями
+pub fn create_tridiagonal_toeplitz(n: usize, sub: f64, diagonal: f64, sup: f64) -> CsrMatrix {
+    CsrMatrix::from_bands(n, &[(-1, sub), (0, diagonal), (1, sup)])
+}
+
+// собственные значения известны точно: diagonal + 2 sqrt(sub sup) cos(k pi / (n + 1)), k = 1..n;
+// при sub * sup < 0 они комплексные - ошибка
+pub fn tridiagonal_toeplitz_eigenvalues(n: usize, sub: f64, diagonal: f64, sup: f64) -> Result<Vec<f64>, SolverError> {
+    if sub * sup < 0.0 {
+        return Err(SolverError::InvalidParameter { name: "sub * sup", value: sub * sup });
+    }
+    Ok((1..=n).map(|k| diagonal + 2.0 * (sub * sup).sqrt() * (k as f64 * std::f64::consts::PI / (n + 1) as f64).cos()).collect())
+}
+
+// -u'' на n внутренних узлах, без множителя 1 / h^2
+pub fn create_poisson_1d(n: usize) -> CsrMatrix {
+    create_tridiagonal_toeplitz(n, -1.0, 2.0, -1.0)
+}
+
+// пятиточечный шаблон на сетке m x m, узел (i, j) имеет номер i * m + j
+pub fn create_poisson_2d(m: usize) -> CsrMatrix {
+    let index = |i: usize, j: usize| i * m + j;
+    let mut triplets = Vec::with_capacity(5 * m * m);
+
+    for i in 0..m {
+        for j in 0..m {
+            let row = index(i, j);
+            triplets.push((row, row, 4.0));
+            if i > 0 { triplets.push((row, index(i - 1, j), -1.0)); }
+            if i + 1 < m { triplets.push((row, index(i + 1, j), -1.0)); }
+            if j > 0 { triplets.push((row, index(i, j - 1), -1.0)); }
+            if j + 1 < m { triplets.push((row, index(i, j + 1), -1.0)); }
+        }
+    }
+
+    CsrMatrix::from_triplets(m * m, m * m, &triplets)
+}
+
+// семиточечный шаблон на сетке m x m x m
+pub fn create_poisson_3d(m: usize) -> CsrMatrix {
+    let index = |i: usize, j: usize, k: usize| (i * m + j) * m + k;
+    let mut triplets = Vec::with_capacity(7 * m * m * m);
+
+    for i in 0..m {
+        for j in 0..m {
+            for k in 0..m {
+                let row = index(i, j, k);
+                triplets.push((row, row, 6.0));
+                if i > 0 { triplets.push((row, index(i - 1, j, k), -1.0)); }
+                if i + 1 < m { triplets.push((row, index(i + 1, j, k), -1.0)); }
+                if j > 0 { triplets.push((row, index(i, j - 1, k), -1.0)); }
+                if j + 1 < m { triplets.push((row, index(i, j + 1, k), -1.0)); }
+                if k > 0 { triplets.push((row, index(i, j, k - 1), -1.0)); }
+                if k + 1 < m { triplets.push((row, index(i, j, k + 1), -1.0)); }
+            }
+        }
+    }
+
+    CsrMatrix::from_triplets(m * m * m, m * m * m, &triplets)
+}
+
+// та же двумерная задача в блочно-трёхдиагональном виде: блоки tridiag(-1, 4, -1) и -I вне диагонали
+pub fn create_poisson_2d_blocks(m: usize) -> Result<BlockTridiagonal, SolverError> {
+    let diagonal = DMatrix::from_fn(m, m, |i, j| if i == j { 4.0 } else if i.abs_diff(j) == 1 { -1.0 } else { 0.0 });
+    let coupling = -DMatrix::<f64>::identity(m, m);
+    BlockTridiagonal::new(vec![coupling.clone(); m.saturating_sub(1)], vec![diagonal; m], vec![coupling; m.saturating_sub(1)])
+}
+
+// несимметричная матрица: -u'' + c u' на равномерной сетке, центральные разности;
+// peclet = c h / 2 - сеточное число Пекле
+pub fn create_convection_diffusion_matrix(n: usize, peclet: f64) -> CsrMatrix {
+    CsrMatrix::from_bands(n, &[(-1, -1.0 - peclet), (0, 2.0), (1, -1.0 + peclet)])
+}
+
+// A = Q diag(lambda) Q^T, lambda от 1 до 1 / condition_number в геометрической прогрессии,
+// Q - ортогональный множитель QR случайной матрицы; cond_2(A) = condition_number точно;
+// condition_number < 1 и вырожденная случайная матрица - ошибки
+pub fn create_random_spd(n: usize, condition_number: f64, seed: u64) -> Result<DMatrix<f64>, SolverError> {
+    if condition_number.is_nan() || condition_number < 1.0 {
+        return Err(SolverError::InvalidParameter { name: "condition number", value: condition_number });
+    }
+    let mut random = SplitMix64::new(seed);
+    let gaussian_like = DMatrix::from_fn(n, n, |_, _| random.next_symmetric());
+    let q = QrFactorization::new(&gaussian_like)?.q();
+
+    let eigenvalues = DVector::from_fn(n, |i, _| {
+        if n > 1 { condition_number.powf(-(i as f64) / (n - 1) as f64) } else { 1.0 }
+    });
+    let a = &q * DMatrix::from_diagonal(&eigenvalues) * q.transpose();
+
+    // убираем несимметричность от округлений
+    Ok((&a + a.transpose()) * 0.5)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExactSolution {
+    Ones,
+    // x_i = sin(pi (i + 1) / (n + 1)) - гладкое решение, как у сеточных задач
+    Smooth,
+    Random(u64),
+}
+
+impl ExactSolution {
+    pub fn vector(&self, n: usize) -> DVector<f64> {
+        match self {
+            ExactSolution::Ones => DVector::from_element(n, 1.0),
+            ExactSolution::Smooth => DVector::from_fn(n, |i, _| (std::f64::consts::PI * (i + 1) as f64 / (n + 1) as f64).sin()),
+            ExactSolution::Random(seed) => {
+                let mut random = SplitMix64::new(*seed);
+                DVector::from_fn(n, |_, _| random.next_symmetric())
+            }
+        }
+    }
+}
+
+// матрица, правая часть и, если задано, точное решение для измерения прямой ошибки
+pub struct TestProblem {
+    pub name: String,
+    pub matrix: Box<dyn SystemMatrix>,
+    pub f: DVector<f64>,
+    pub exact_solution: Option<DVector<f64>>,
+}
+
+impl TestProblem {
+    pub fn new(name: &str, matrix: impl SystemMatrix + 'static, f: DVector<f64>) -> Self {
+        TestProblem { name: name.to_string(), matrix: Box::new(matrix), f, exact_solution: None }
+    }
+
+    // f = A x*, так что x* известно точно (с точностью до округления при умножении)
+    pub fn with_exact_solution(name: &str, matrix: impl SystemMatrix + 'static, exact_solution: &ExactSolution) -> Self {
+        let x = exact_solution.vector(matrix.ncols());
+        let f = matrix.mul_vec(&x);
+        TestProblem { name: name.to_string(), matrix: Box::new(matrix), f, exact_solution: Some(x) }
+    }
+
+    // ||x - x*|| / ||x*||
+    pub fn forward_error(&self, x: &DVector<f64>) -> Option<f64> {
+        self.exact_solution.as_ref().map(|exact| (x - exact).norm() / exact.norm())
+    }
+
+    pub fn residual_norm(&self, x: &DVector<f64>) -> f64 {
+        (&self.f - self.matrix.mul_vec(x)).norm()
+    }
+}
+
+// стандартный набор тестовых задач с точным решением, размеры подобраны для плотных методов
+pub fn standard_test_problems(exact_solution: &ExactSolution) -> Result<Vec<TestProblem>, SolverError> {
+    Ok(vec![
+        TestProblem::with_exact_solution("custom", create_custom_matrix(100, 10.0), exact_solution),
+        TestProblem::with_exact_solution("hilbert_8", create_hilbert_matrix(8), exact_solution),
+        TestProblem::with_exact_solution("vandermonde_10", create_vandermonde_matrix(&uniform_nodes(10)), exact_solution),
+        TestProblem::with_exact_solution("poisson_1d", create_poisson_1d(100), exact_solution),
+        TestProblem::with_exact_solution("poisson_2d", create_poisson_2d(15), exact_solution),
+        TestProblem::with_exact_solution("poisson_3d", create_poisson_3d(6), exact_solution),
+        TestProblem::with_exact_solution("random_spd_1e6", create_random_spd(100, 1e6, 42)?, exact_solution),
+        TestProblem::with_exact_solution("toeplitz", create_tridiagonal_toeplitz(100, -1.0, 3.0, -1.0), exact_solution),
+        TestProblem::with_exact_solution("convection_diffusion", create_convection_diffusion_matrix(100, 0.5), exact_solution),
+    ])
+}
+
+// SplitMix64: воспроизводимые псевдослучайные числа без внешних зависимостей
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // равномерно на [-1, 1)
+    fn next_symmetric(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse_custom_matrix_matches_dense() {
+        assert_eq!(create_custom_sparse_matrix(7, 10.0).to_dense(), create_custom_matrix(7, 10.0));
+    }
+
+    #[test]
+    fn poisson_2d_blocks_match_sparse_matrix() {
+        let blocks = create_poisson_2d_blocks(4).unwrap();
+        let x = ExactSolution::Random(3).vector(16);
+        assert!((blocks.mul_vec(&x) - create_poisson_2d(4).mul_vec(&x)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn poisson_rows_sum_to_zero_inside_the_grid() {
+        let matrix = create_poisson_3d(3);
+        // центральный узел сетки 3 x 3 x 3 не касается границы
+        let center = 13;
+        assert_eq!(matrix.mul_vec(&DVector::from_element(27, 1.0))[center], 0.0);
+    }
+
+    #[test]
+    fn toeplitz_eigenvalues_match_dense_spectrum() {
+        let dense = create_tridiagonal_toeplitz(6, -1.0, 3.0, -1.0).to_dense();
+        let mut expected: Vec<f64> = dense.symmetric_eigenvalues().iter().copied().collect();
+        let mut eigenvalues = tridiagonal_toeplitz_eigenvalues(6, -1.0, 3.0, -1.0).unwrap();
+        expected.sort_by(f64::total_cmp);
+        eigenvalues.sort_by(f64::total_cmp);
+
+        for (lambda, mu) in eigenvalues.iter().zip(&expected) {
+            assert!((lambda - mu).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn random_spd_is_reproducible_and_symmetric() {
+        let matrix = create_random_spd(10, 100.0, 7).unwrap();
+        assert_eq!(matrix, create_random_spd(10, 100.0, 7).unwrap());
+        assert_eq!(matrix, matrix.transpose());
+
+        let eigenvalues = matrix.symmetric_eigenvalues();
+        assert!((eigenvalues.max() / eigenvalues.min() - 100.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn invalid_generator_parameters_are_errors() {
+        assert!(matches!(create_random_spd(5, 0.5, 1), Err(SolverError::InvalidParameter { .. })));
+        assert!(matches!(create_random_spd(5, f64::NAN, 1), Err(SolverError::InvalidParameter { .. })));
+        assert!(matches!(tridiagonal_toeplitz_eigenvalues(5, -1.0, 2.0, 1.0), Err(SolverError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn exact_solution_gives_consistent_problem() {
+        let problem = TestProblem::with_exact_solution("poisson", create_poisson_1d(10), &ExactSolution::Smooth);
+        let exact = problem.exact_solution.clone().unwrap();
+
+        assert_eq!(problem.forward_error(&exact), Some(0.0));
+        assert!(problem.residual_norm(&exact) < 1e-15);
+        assert_eq!(TestProblem::new("f", create_poisson_1d(3), create_vector_f(3)).forward_error(&DVector::zeros(3)), None);
+    }
+
+    #[test]
+    fn empty_generators() {
+        assert_eq!(create_poisson_1d(0).nrows(), 0);
+        assert_eq!(create_poisson_2d(0).nrows(), 0);
+        assert!(create_poisson_2d_blocks(0).unwrap().mul_vec(&DVector::zeros(0)).is_empty());
+        assert!(uniform_nodes(0).is_empty());
+        assert_eq!(uniform_nodes(1), vec![0.0]);
+        assert!(create_vector_f(0).is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::preconditioner::{precondition, Preconditioner, PreconditionerSide, Preconditioning};
+use super::solver::{LinearSolver, SolveReport, SolverError, StopReason};
+use super::stopping::StoppingCriterion;
+
+// GMRES(m): Арнольди с модифицированным Грамом-Шмидтом, МНК-задача решается вращениями Гивенса
+pub struct Gmres {
+    // длина цикла перезапуска; 0 - ошибка InvalidParameter при решении
+    pub restart: usize,
+    pub criterion: StoppingCriterion,
+    pub preconditioning: Option<Preconditioning>,
+}
+
+impl Gmres {
+    pub fn new(restart: usize, tolerance: f64, max_iterations: usize) -> Self {
+        Gmres { restart, criterion: StoppingCriterion::absolute_residual(tolerance, max_iterations), preconditioning: None }
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    pub fn with_preconditioner(mut self, preconditioner: Box<dyn Preconditioner>, side: PreconditionerSide) -> Self {
+        self.preconditioning = Some(Preconditioning { preconditioner, side });
+        self
+    }
+}
+
+fn givens_rotation(a: f64, b: f64) -> (f64, f64) {
+    if b == 0.0 {
+        (1.0, 0.0)
+    } else {
+        let r = a.hypot(b);
+        (a / r, b / r)
+    }
+}
+
+impl LinearSolver for Gmres {
+    fn name(&self) -> &str {
+        "GMRES"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, f, &mut ())
+    }
+
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        if self.restart == 0 {
+            let report = SolveReport::failed(matrix.nrows(), SolverError::InvalidParameter { name: "restart", value: 0.0 });
+            observer.on_finish(&report);
+            return report;
+        }
+
+        let preconditioning = self.preconditioning.as_ref();
+        let operator = |v: &DVector<f64>| {
+            let w = matrix.mul_vec(&precondition(preconditioning, PreconditionerSide::Right, v.clone()));
+            precondition(preconditioning, PreconditionerSide::Left, w)
+        };
+        let f = &precondition(preconditioning, PreconditionerSide::Left, f.clone());
+
+        let n = matrix.nrows();
+        let m = self.restart.min(n);
+        let mut x = DVector::zeros(n);
+        let mut r = f - operator(&x);
+        let mut beta = r.norm();
+        let mut monitor = self.criterion.monitor(f).with_observer(observer);
+        let max_iterations = monitor.max_iterations();
+        let mut iteration = 0;
+        let label = format!("GMRES({}) method", m);
+
+        // пустая система или точное x0: базис Крылова r / beta не определён, и при n = 0 цикл не продвигается
+        if monitor.initially_converged(beta) {
+            monitor.announce(&label, 0, StopReason::Converged);
+            return monitor.finish(x, 0, StopReason::Converged);
+        }
+
+        while iteration < max_iterations {
+            let mut basis: Vec<DVector<f64>> = vec![&r / beta];
+            let mut h = DMatrix::zeros(m + 1, m);
+            let mut cs = vec![0.0; m];
+            let mut sn = vec![0.0; m];
+            let mut g = DVector::zeros(m + 1);
+            g[0] = beta;
+
+            let mut k = 0;
+            let mut stop_reason = None;
+            while k < m && iteration < max_iterations {
+                let mut w = operator(&basis[k]);
+                for (i, v) in basis.iter().enumerate() {
+                    h[(i, k)] = w.dot(v);
+                    w -= h[(i, k)] * v;
+                }
+                h[(k + 1, k)] = w.norm();
+
+                for i in 0..k {
+                    let temp = cs[i] * h[(i, k)] + sn[i] * h[(i + 1, k)];
+                    h[(i + 1, k)] = -sn[i] * h[(i, k)] + cs[i] * h[(i + 1, k)];
+                    h[(i, k)] = temp;
+                }
+
+                let (c, s) = givens_rotation(h[(k, k)], h[(k + 1, k)]);
+                cs[k] = c;
+                sn[k] = s;
+                let lucky_breakdown = h[(k + 1, k)] == 0.0;
+                if !lucky_breakdown {
+                    basis.push(w / h[(k + 1, k)]);
+                }
+                h[(k, k)] = c * h[(k, k)] + s * h[(k + 1, k)];
+                h[(k + 1, k)] = 0.0;
+                g[k + 1] = -s * g[k];
+                g[k] *= c;
+
+                // внутри цикла x не строится, поэтому норма шага недоступна
+                stop_reason = monitor.check(g[k + 1].abs(), None);
+                iteration += 1;
+                k += 1;
+
+                if stop_reason.is_some() || lucky_breakdown {
+                    break;
+                }
+            }
+
+            // обратный ход для верхнетреугольной H y = g
+            let mut y = DVector::zeros(k);
+            for i in (0..k).rev() {
+                let sum: f64 = (i + 1..k).map(|j| h[(i, j)] * y[j]).sum();
+                y[i] = (g[i] - sum) / h[(i, i)];
+            }
+            for (i, v) in basis.iter().take(k).enumerate() {
+                x += y[i] * v;
+            }
+
+            if let Some(stop_reason) = stop_reason {
+                monitor.announce(&label, iteration, stop_reason);
+                let solution = precondition(preconditioning, PreconditionerSide::Right, x);
+                return monitor.finish(solution, iteration, stop_reason);
+            }
+
+            r = f - operator(&x);
+            beta = r.norm();
+            if beta == 0.0 {
+                monitor.announce(&label, iteration, StopReason::Converged);
+                let solution = precondition(preconditioning, PreconditionerSide::Right, x);
+                return monitor.finish(solution, iteration, StopReason::Converged);
+            }
+        }
+
+        monitor.announce(&label, max_iterations, StopReason::MaxIterations);
+        let solution = precondition(preconditioning, PreconditionerSide::Right, x);
+        monitor.finish(solution, max_iterations, StopReason::MaxIterations)
+    }
+}
+
+pub fn gmres_solve(matrix: &dyn SystemMatrix, f: &DVector<f64>, restart: usize, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = Gmres::new(restart, tolerance, max_iterations).solve(matrix, f);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_convection_diffusion_matrix, create_poisson_1d, create_vector_f};
+    use crate::methods::preconditioner::Ilu0;
+
+    #[test]
+    fn solves_nonsymmetric_system() {
+        let matrix = create_convection_diffusion_matrix(40, 5.0);
+        let f = create_vector_f(40);
+        let report = Gmres::new(10, 1e-10, 2000).solve(&matrix, &f);
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-8);
+    }
+
+    // без рестартов GMRES точен не более чем за n шагов
+    #[test]
+    fn full_gmres_finishes_in_n_steps() {
+        let matrix = create_convection_diffusion_matrix(12, 2.0);
+        let report = Gmres::new(12, 1e-12, 100).solve(&matrix, &create_vector_f(12));
+        assert!(report.converged);
+        assert!(report.iterations <= 12);
+    }
+
+    #[test]
+    fn right_preconditioning_returns_original_unknowns() {
+        let matrix = create_convection_diffusion_matrix(30, 5.0);
+        let f = create_vector_f(30);
+        let report = Gmres::new(10, 1e-10, 1000)
+            .with_preconditioner(Box::new(Ilu0::new(&matrix).unwrap()), PreconditionerSide::Right)
+            .solve(&matrix, &f);
+
+        assert!(report.converged);
+        assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-8);
+    }
+
+    #[test]
+    fn empty_system_converges_immediately() {
+        let report = Gmres::new(20, 1e-10, 100).solve(&create_poisson_1d(0), &DVector::zeros(0));
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert_eq!(report.solution.len(), 0);
+        assert_eq!(report.residuals, vec![0.0]);
+    }
+
+    #[test]
+    fn zero_restart_is_an_error() {
+        let report = Gmres::new(0, 1e-10, 100).solve(&create_poisson_1d(5), &create_vector_f(5));
+        assert!(matches!(report.error, Some(SolverError::InvalidParameter { name: "restart", .. })));
+        assert!(!report.converged);
+    }
+
+    #[test]
+    fn zero_rhs_gives_zero_solution() {
+        let solver = Gmres::new(5, 1e-10, 100).with_criterion(StoppingCriterion::relative_residual(1e-10, 100));
+        let report = solver.solve(&create_poisson_1d(5), &DVector::zeros(5));
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert_eq!(report.solution, DVector::zeros(5));
+    }
+}
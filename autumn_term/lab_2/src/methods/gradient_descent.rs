@@ -1,26 +1,258 @@
-use nalgebra::{DMatrix, DVector};
+use nalgebra::DVector;
 
-pub fn gradient_descent_solve(matrix: &DMatrix<f64>, f: &DVector<f64>, learning_rate: f64, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+use super::conditioning::estimate_spectral_radius;
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::solver::{LinearSolver, SolveReport, SolverError, StopReason};
+use super::stopping::StoppingCriterion;
+
+// все стратегии минимизируют 0.5 ||A x - f||^2, т.е. работают с нормальной системой A^T A x = A^T f
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepStrategy {
+    Fixed(f64),
+    // точный минимум квадратичной функции вдоль антиградиента
+    ExactLineSearch,
+    // шаг Барзилаи-Борвейна s^T s / s^T y
+    BarzilaiBorwein,
+    // ускорение Ричардсона полиномами Чебышёва, нужны границы спектра A^T A
+    Chebyshev { lambda_min: f64, lambda_max: f64 },
+}
+
+pub struct GradientDescent {
+    pub step: StepStrategy,
+    pub criterion: StoppingCriterion,
+}
+
+impl GradientDescent {
+    pub fn new(learning_rate: f64, tolerance: f64, max_iterations: usize) -> Self {
+        GradientDescent { step: StepStrategy::Fixed(learning_rate), criterion: StoppingCriterion::absolute_residual(tolerance, max_iterations) }
+    }
+
+    pub fn with_strategy(step: StepStrategy, tolerance: f64, max_iterations: usize) -> Self {
+        GradientDescent { step, criterion: StoppingCriterion::absolute_residual(tolerance, max_iterations) }
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+}
+
+// границы спектра A^T A: lambda_max степенным методом, lambda_min через сдвиг lambda_max I - A^T A;
+// степенной метод недооценивает радиус, поэтому границы немного расширяются
+pub fn normal_spectrum_bounds(matrix: &dyn SystemMatrix, tolerance: f64, max_iterations: usize) -> (f64, f64) {
+    let n = matrix.ncols();
+    let normal = |v: &DVector<f64>| matrix.tr_mul_vec(&matrix.mul_vec(v));
+    let lambda_max = estimate_spectral_radius(&normal, n, tolerance, max_iterations);
+    let shifted = |v: &DVector<f64>| lambda_max * v - normal(v);
+    let lambda_min = lambda_max - estimate_spectral_radius(&shifted, n, tolerance, max_iterations);
+
+    (0.95 * lambda_min.max(0.0), 1.05 * lambda_max)
+}
+
+impl LinearSolver for GradientDescent {
+    fn name(&self) -> &str {
+        match self.step {
+            StepStrategy::Fixed(_) => "Gradient descent",
+            StepStrategy::ExactLineSearch => "Steepest descent",
+            StepStrategy::BarzilaiBorwein => "Barzilai-Borwein gradient descent",
+            StepStrategy::Chebyshev { .. } => "Chebyshev-accelerated Richardson",
+        }
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, f, &mut ())
+    }
+
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        if let StepStrategy::Chebyshev { lambda_min, lambda_max } = self.step {
+            return chebyshev_solve(matrix, f, lambda_min, lambda_max, &self.criterion, self.name(), observer);
+        }
+
+        let n = matrix.nrows();
+        let mut x = DVector::zeros(n); 
+        let mut r = f - matrix.mul_vec(&x); 
+        let mut monitor = self.criterion.monitor(f).with_observer(observer);
+        // предыдущий шаг и направление для формулы Барзилаи-Борвейна
+        let mut previous: Option<(DVector<f64>, DVector<f64>)> = None;
+
+        for iteration in 0..monitor.max_iterations() {
+            
+            let gradient = matrix.tr_mul_vec(&r);
+
+            let learning_rate = match &self.step {
+                StepStrategy::Fixed(learning_rate) => *learning_rate,
+                StepStrategy::BarzilaiBorwein if previous.is_some() => {
+                    let (step, previous_gradient) = previous.as_ref().unwrap();
+                    // y = A^T A s = previous_gradient - gradient
+                    let sy = step.dot(&(previous_gradient - &gradient));
+                    if sy > 0.0 { step.norm_squared() / sy } else { exact_step(matrix, &gradient) }
+                }
+                _ => exact_step(matrix, &gradient),
+            };
+
+            let step = learning_rate * &gradient;
+            x += &step;
+
+            r = f - matrix.mul_vec(&x);
+
+            if let Some(stop_reason) = monitor.check(r.norm(), Some(step.norm())) {
+                monitor.announce(self.name(), iteration + 1, stop_reason);
+                return monitor.finish(x, iteration + 1, stop_reason);
+            }
+
+            if self.step == StepStrategy::BarzilaiBorwein {
+                previous = Some((step, gradient));
+            }
+        }
+
+        let max_iterations = monitor.max_iterations();
+        monitor.announce(self.name(), max_iterations, StopReason::MaxIterations);
+        monitor.finish(x, max_iterations, StopReason::MaxIterations)
+    }
+}
+
+// argmin_alpha ||f - A (x + alpha g)||^2 = ||g||^2 / ||A g||^2 при g = A^T r
+fn exact_step(matrix: &dyn SystemMatrix, gradient: &DVector<f64>) -> f64 {
+    let a_gradient = matrix.mul_vec(gradient);
+    let denominator = a_gradient.norm_squared();
+    if denominator == 0.0 {
+        return 0.0;
+    }
+
+    gradient.norm_squared() / denominator
+}
+
+// трёхчленная рекуррентная формула Чебышёва для A^T A x = A^T f (Saad, алгоритм 12.1)
+fn chebyshev_solve(matrix: &dyn SystemMatrix, f: &DVector<f64>, lambda_min: f64, lambda_max: f64, criterion: &StoppingCriterion, name: &str, observer: &mut dyn IterationObserver) -> SolveReport {
     let n = matrix.nrows();
-    let mut x = DVector::zeros(n); 
-    let mut r = f - matrix * &x; 
-    let mut residuals = Vec::new(); 
+    // lambda_min = 0 - A^T A вырождена или оценка снизу не удалась
+    if !(0.0 < lambda_min && lambda_min < lambda_max) {
+        let report = SolveReport::failed(n, SolverError::SpectrumBounds { lambda_min, lambda_max });
+        observer.on_finish(&report);
+        return report;
+    }
 
-    for iteration in 0..max_iterations {
-        
-        let gradient = matrix.transpose() * &r;
+    let theta = (lambda_max + lambda_min) / 2.0;
+    let delta = (lambda_max - lambda_min) / 2.0;
+    let sigma = theta / delta;
 
-        x += learning_rate * &gradient;
+    let mut x = DVector::zeros(n);
+    let mut r = f - matrix.mul_vec(&x);
+    let mut normal_residual = matrix.tr_mul_vec(&r);
+    let mut rho = 1.0 / sigma;
+    let mut d = &normal_residual / theta;
+    let mut monitor = criterion.monitor(f).with_observer(observer);
 
-        r = f - matrix * &x;
-        residuals.push(r.norm()); 
+    for iteration in 0..monitor.max_iterations() {
+        x += &d;
+        r = f - matrix.mul_vec(&x);
 
-        if r.norm() < tolerance {
-            println!("Gradient descent converged in {} iterations", iteration + 1);
-            return (x, residuals);
+        if let Some(stop_reason) = monitor.check(r.norm(), Some(d.norm())) {
+            monitor.announce(name, iteration + 1, stop_reason);
+            return monitor.finish(x, iteration + 1, stop_reason);
         }
+
+        normal_residual = matrix.tr_mul_vec(&r);
+        let rho_next = 1.0 / (2.0 * sigma - rho);
+        d = rho_next * rho * &d + (2.0 * rho_next / delta) * &normal_residual;
+        rho = rho_next;
     }
 
-    println!("Warning!!! Gradient descent did not converge in {} iterations.", max_iterations);
-    (x, residuals)
+    let max_iterations = monitor.max_iterations();
+    monitor.announce(name, max_iterations, StopReason::MaxIterations);
+    monitor.finish(x, max_iterations, StopReason::MaxIterations)
+}
+
+pub fn gradient_descent_solve(matrix: &dyn SystemMatrix, f: &DVector<f64>, learning_rate: f64, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = GradientDescent::new(learning_rate, tolerance, max_iterations).solve(matrix, f);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_vector_f};
+
+    fn system() -> (DMatrix<f64>, DVector<f64>) {
+        (create_custom_sparse_matrix(20, 10.0).to_dense(), create_vector_f(20))
+    }
+
+    #[test]
+    fn every_strategy_solves_the_system() {
+        let (matrix, f) = system();
+        let (lambda_min, lambda_max) = normal_spectrum_bounds(&matrix, 1e-10, 10000);
+        let strategies = [
+            StepStrategy::Fixed(1.0 / lambda_max),
+            StepStrategy::ExactLineSearch,
+            StepStrategy::BarzilaiBorwein,
+            StepStrategy::Chebyshev { lambda_min, lambda_max },
+        ];
+
+        for strategy in strategies {
+            let report = GradientDescent::with_strategy(strategy.clone(), 1e-10, 100000).solve(&matrix, &f);
+            assert!(report.converged, "{:?}", strategy);
+            assert!((&f - &matrix * &report.solution).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn spectrum_bounds_enclose_the_spectrum() {
+        let (matrix, _) = system();
+        let eigenvalues = (matrix.transpose() * &matrix).symmetric_eigenvalues();
+        let (lambda_min, lambda_max) = normal_spectrum_bounds(&matrix, 1e-12, 100000);
+
+        assert!(lambda_min <= eigenvalues.min() && eigenvalues.max() <= lambda_max);
+        assert!(lambda_min > 0.0);
+    }
+
+    #[test]
+    fn chebyshev_is_faster_than_steepest_descent() {
+        let (matrix, f) = system();
+        let (lambda_min, lambda_max) = normal_spectrum_bounds(&matrix, 1e-10, 10000);
+        let steepest = GradientDescent::with_strategy(StepStrategy::ExactLineSearch, 1e-10, 100000).solve(&matrix, &f);
+        let chebyshev = GradientDescent::with_strategy(StepStrategy::Chebyshev { lambda_min, lambda_max }, 1e-10, 100000).solve(&matrix, &f);
+
+        assert!(chebyshev.iterations < steepest.iterations);
+    }
+
+    #[test]
+    fn zero_rhs_gives_zero_solution() {
+        let (matrix, _) = system();
+        let solver = GradientDescent::with_strategy(StepStrategy::ExactLineSearch, 1e-10, 100).with_criterion(StoppingCriterion::relative_residual(1e-10, 100));
+        let report = solver.solve(&matrix, &DVector::zeros(20));
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert_eq!(report.solution, DVector::zeros(20));
+    }
+
+    // у вырожденной матрицы lambda_min(A^T A) = 0, итерации Чебышёва неприменимы
+    #[test]
+    fn chebyshev_rejects_singular_spectrum() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 1.0, 1.0, 1.0]);
+        let (lambda_min, lambda_max) = normal_spectrum_bounds(&matrix, 1e-12, 10000);
+        let report = GradientDescent::with_strategy(StepStrategy::Chebyshev { lambda_min, lambda_max }, 1e-10, 100).solve(&matrix, &DVector::from_element(2, 1.0));
+
+        assert_eq!(lambda_min, 0.0);
+        assert!(!report.converged);
+        assert!(matches!(report.error, Some(SolverError::SpectrumBounds { .. })));
+    }
+
+    // спуск идёт по нормальной системе, нулевая диагональ A ему не мешает
+    #[test]
+    fn zero_diagonal_and_empty_system() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let f = DVector::from_vec(vec![1.0, 2.0]);
+        for strategy in [StepStrategy::ExactLineSearch, StepStrategy::BarzilaiBorwein] {
+            let report = GradientDescent::with_strategy(strategy.clone(), 1e-12, 100).solve(&matrix, &f);
+            assert!(report.converged, "{:?}", strategy);
+            assert!((&report.solution - DVector::from_vec(vec![2.0, 1.0])).norm() < 1e-10);
+        }
+
+        let report = GradientDescent::with_strategy(StepStrategy::ExactLineSearch, 1e-10, 100).solve(&DMatrix::zeros(0, 0), &DVector::zeros(0));
+        assert!(report.converged);
+        assert!(report.solution.is_empty());
+    }
 }
@@ -1,35 +1,166 @@
-use nalgebra::{DMatrix, DVector};
-
-pub fn jacobi_solve(matrix: &DMatrix<f64>, b: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
-    let n = matrix.nrows();
-    let mut x = DVector::zeros(n); 
-    let mut x_new = x.clone(); 
-    let mut residuals = Vec::new(); // Вектор для хранения невязок
-
-    for iteration in 0..max_iterations {
-        for i in 0..n {
-            let mut sum = 0.0;
-
-            for j in 0..n {
-                if i != j {
-                    sum += matrix[(i, j)] * x[j];
-                }
-            }
+use nalgebra::DVector;
+
+use super::diagnostics::jacobi_precheck;
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::parallel::{map_rows, mul_vec, Execution};
+use super::solver::{LinearSolver, SolveReport, StopReason};
+use super::stopping::StoppingCriterion;
+
+pub struct Jacobi {
+    pub criterion: StoppingCriterion,
+    // дешёвая проверка до начала итераций: квадратная матрица без нулей на диагонали
+    pub precheck: bool,
+    pub execution: Execution,
+}
+
+impl Jacobi {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Jacobi { criterion: StoppingCriterion::step_norm(tolerance, max_iterations), precheck: true, execution: Execution::Serial }
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    pub fn without_precheck(mut self) -> Self {
+        self.precheck = false;
+        self
+    }
+
+    pub fn with_execution(mut self, execution: Execution) -> Self {
+        self.execution = execution;
+        self
+    }
+}
+
+impl LinearSolver for Jacobi {
+    fn name(&self) -> &str {
+        "Jacobi"
+    }
 
-            x_new[i] = (b[i] - sum) / matrix[(i, i)];
+    fn solve(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, b, &mut ())
+    }
+
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let n = matrix.nrows();
+        if self.precheck {
+            if let Err(error) = jacobi_precheck(matrix) {
+                let report = SolveReport::precheck_failed(n, error);
+                observer.on_finish(&report);
+                return report;
+            }
         }
 
-        let residual = b - matrix * &x_new;
-        residuals.push(residual.norm());
+        let mut x = DVector::zeros(n); 
+        let mut x_new = x.clone(); 
 
-        if (&x_new - &x).norm() < tolerance {
-            println!("The Jacobi method converged in {} iterations", iteration + 1);
-            return (x_new, residuals);
+        let mut monitor = self.criterion.monitor(b).with_observer(observer);
+
+        for iteration in 0..monitor.max_iterations() {
+            x_new = jacobi_sweep(matrix, b, &x, 1.0, self.execution);
+
+            let residual = b - mul_vec(self.execution, matrix, &x_new);
+
+            if let Some(stop_reason) = monitor.check(residual.norm(), Some((&x_new - &x).norm())) {
+                monitor.announce("The Jacobi method", iteration + 1, stop_reason);
+                return monitor.finish(x_new, iteration + 1, stop_reason);
+            }
+
+            x = x_new.clone();
         }
 
-        x = x_new.clone();
+        let max_iterations = monitor.max_iterations();
+        monitor.announce("The Jacobi method", max_iterations, StopReason::MaxIterations);
+        monitor.finish(x_new, max_iterations, StopReason::MaxIterations)
+    }
+}
+
+// один шаг взвешенного метода Якоби: x_i <- (1 - weight) x_i + weight (b_i - sum_{j != i} a_ij x_j) / a_ii;
+// weight < 1 используется как сглаживатель в многосеточном методе
+pub fn jacobi_sweep(matrix: &dyn SystemMatrix, b: &DVector<f64>, x: &DVector<f64>, weight: f64, execution: Execution) -> DVector<f64> {
+    let diagonal = matrix.diagonal();
+
+    // все компоненты считаются по старому x независимо друг от друга
+    map_rows(execution, matrix.nrows(), |i| {
+        let sum = matrix.row_dot(i, x) - diagonal[i] * x[i];
+
+        (1.0 - weight) * x[i] + (weight * (b[i] - sum)) / diagonal[i]
+    })
+}
+
+pub fn jacobi_solve(matrix: &dyn SystemMatrix, b: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = Jacobi::new(tolerance, max_iterations).solve(matrix, b);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_poisson_2d, create_vector_f};
+    use crate::methods::solver::SolverError;
+
+    #[test]
+    fn solves_banded_system() {
+        let matrix = create_custom_sparse_matrix(50, 10.0);
+        let f = create_vector_f(50);
+        let report = Jacobi::new(1e-12, 1000).solve(&matrix, &f);
+
+        assert!(report.converged);
+        assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-10);
+    }
+
+    // weight = 1 / 2 оставляет половину старого значения
+    #[test]
+    fn weighted_sweep_averages_with_previous_iterate() {
+        let matrix = create_poisson_2d(4);
+        let f = create_vector_f(16);
+        let x = DVector::from_element(16, 1.0);
+        let full = jacobi_sweep(&matrix, &f, &x, 1.0, Execution::Serial);
+        let half = jacobi_sweep(&matrix, &f, &x, 0.5, Execution::Serial);
+
+        assert!((half - (&x + &full) * 0.5).norm() < 1e-12);
+    }
+
+    #[test]
+    fn zero_diagonal_fails_precheck() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let report = Jacobi::new(1e-10, 100).solve(&matrix, &DVector::from_element(2, 1.0));
+
+        assert_eq!(report.stop_reason, StopReason::PrecheckFailed);
+        assert!(matches!(report.error, Some(SolverError::Precheck(_))));
+    }
+
+    #[test]
+    fn empty_system_converges() {
+        let report = Jacobi::new(1e-10, 100).solve(&create_custom_sparse_matrix(0, 10.0), &DVector::zeros(0));
+        assert!(report.converged);
+        assert!(report.solution.is_empty());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_iterations_match_serial() {
+        let matrix = create_poisson_2d(10);
+        let f = create_vector_f(100);
+        let serial = Jacobi::new(1e-8, 10000).solve(&matrix, &f);
+        let parallel = Jacobi::new(1e-8, 10000).with_execution(Execution::Parallel).solve(&matrix, &f);
+
+        assert_eq!(serial.iterations, parallel.iterations);
+        assert!((serial.solution - parallel.solution).norm() < 1e-12);
     }
 
-    println!("Warning!!! The Jacobi method did not converge for {} iterations.", max_iterations);
-    (x_new, residuals)
+    #[test]
+    fn zero_rhs_converges_under_relative_criterion() {
+        let solver = Jacobi::new(1e-10, 1000).with_criterion(StoppingCriterion::relative_residual(1e-10, 1000));
+        let report = solver.solve(&create_custom_sparse_matrix(10, 10.0), &DVector::zeros(10));
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert_eq!(report.iterations, 1);
+        assert_eq!(report.solution, DVector::zeros(10));
+    }
 }
@@ -0,0 +1,209 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::matrix::SystemMatrix;
+use super::solver::{check_symmetric, FactorizationError, LinearSolver, SolveReport};
+
+// параметр выбора ведущего элемента Банча-Кауфмана
+const BUNCH_KAUFMAN_ALPHA: f64 = 0.6403882032022076; // (1 + sqrt(17)) / 8
+
+// P A P^T = L D L^T, D - блочно-диагональная с блоками 1x1 и 2x2 (метод Банча-Кауфмана),
+// подходит для симметричных знаконеопределённых матриц
+#[derive(Debug, Clone)]
+pub struct LdltFactorization {
+    l: DMatrix<f64>,
+    d: DMatrix<f64>,
+    block_sizes: Vec<usize>,
+    permutation: Vec<usize>,
+}
+
+impl LdltFactorization {
+    pub fn new(matrix: &DMatrix<f64>) -> Result<Self, FactorizationError> {
+        check_symmetric(matrix)?;
+        let n = matrix.nrows();
+        let mut a = matrix.clone();
+        let mut l = DMatrix::identity(n, n);
+        let mut d = DMatrix::zeros(n, n);
+        let mut block_sizes = Vec::new();
+        let mut permutation: Vec<usize> = (0..n).collect();
+
+        let mut k = 0;
+        while k < n {
+            let diagonal = a[(k, k)].abs();
+            let (imax, column_max) = (k + 1..n)
+                .map(|i| (i, a[(i, k)].abs()))
+                .fold((k, 0.0), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+
+            if diagonal.max(column_max) == 0.0 {
+                return Err(FactorizationError::ZeroPivot { row: k });
+            }
+
+            let (pivot, step) = if diagonal >= BUNCH_KAUFMAN_ALPHA * column_max {
+                (k, 1)
+            } else {
+                let row_max = (k..n).filter(|&j| j != imax).map(|j| a[(imax, j)].abs()).fold(0.0, f64::max);
+                if diagonal * row_max >= BUNCH_KAUFMAN_ALPHA * column_max * column_max {
+                    (k, 1)
+                } else if a[(imax, imax)].abs() >= BUNCH_KAUFMAN_ALPHA * row_max {
+                    (imax, 1)
+                } else {
+                    (imax, 2)
+                }
+            };
+
+            // симметричная перестановка: ведущий элемент встаёт на место k + step - 1
+            let target = k + step - 1;
+            if pivot != target {
+                a.swap_rows(target, pivot);
+                a.swap_columns(target, pivot);
+                permutation.swap(target, pivot);
+                for j in 0..k {
+                    l.swap((target, j), (pivot, j));
+                }
+            }
+
+            if step == 1 {
+                let pivot_value = a[(k, k)];
+                d[(k, k)] = pivot_value;
+                for i in (k + 1)..n {
+                    l[(i, k)] = a[(i, k)] / pivot_value;
+                }
+                for i in (k + 1)..n {
+                    for j in (k + 1)..n {
+                        a[(i, j)] -= l[(i, k)] * a[(k, j)];
+                    }
+                }
+            } else {
+                let (d11, d21, d22) = (a[(k, k)], a[(k + 1, k)], a[(k + 1, k + 1)]);
+                let determinant = d11 * d22 - d21 * d21;
+                d[(k, k)] = d11;
+                d[(k + 1, k)] = d21;
+                d[(k, k + 1)] = d21;
+                d[(k + 1, k + 1)] = d22;
+
+                for i in (k + 2)..n {
+                    let (a1, a2) = (a[(i, k)], a[(i, k + 1)]);
+                    l[(i, k)] = (a1 * d22 - a2 * d21) / determinant;
+                    l[(i, k + 1)] = (a2 * d11 - a1 * d21) / determinant;
+                }
+                for i in (k + 2)..n {
+                    for j in (k + 2)..n {
+                        a[(i, j)] -= l[(i, k)] * a[(j, k)] + l[(i, k + 1)] * a[(j, k + 1)];
+                    }
+                }
+            }
+
+            block_sizes.push(step);
+            k += step;
+        }
+
+        Ok(LdltFactorization { l, d, block_sizes, permutation })
+    }
+
+    pub fn l(&self) -> &DMatrix<f64> {
+        &self.l
+    }
+
+    pub fn d(&self) -> &DMatrix<f64> {
+        &self.d
+    }
+
+    // i-я строка P A P^T - это строка permutation[i] матрицы A
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn solve(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.l.nrows();
+
+        let mut y = DVector::from_fn(n, |i, _| b[self.permutation[i]]);
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| self.l[(i, k)] * y[k]).sum();
+            y[i] -= sum;
+        }
+
+        let mut k = 0;
+        for &step in &self.block_sizes {
+            if step == 1 {
+                y[k] /= self.d[(k, k)];
+            } else {
+                let (d11, d21, d22) = (self.d[(k, k)], self.d[(k + 1, k)], self.d[(k + 1, k + 1)]);
+                let determinant = d11 * d22 - d21 * d21;
+                let (y1, y2) = (y[k], y[k + 1]);
+                y[k] = (d22 * y1 - d21 * y2) / determinant;
+                y[k + 1] = (d11 * y2 - d21 * y1) / determinant;
+            }
+            k += step;
+        }
+
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| self.l[(k, i)] * y[k]).sum();
+            y[i] -= sum;
+        }
+
+        let mut x = DVector::zeros(n);
+        for (i, &row) in self.permutation.iter().enumerate() {
+            x[row] = y[i];
+        }
+
+        x
+    }
+}
+
+pub struct Ldlt;
+
+impl LinearSolver for Ldlt {
+    fn name(&self) -> &str {
+        "LDLT"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        match LdltFactorization::new(&matrix.to_dense()) {
+            Ok(factorization) => SolveReport::direct(factorization.solve(f)),
+            Err(error) => SolveReport::failed(f.len(), error),
+        }
+    }
+}
+
+pub fn ldlt_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> Result<DVector<f64>, FactorizationError> {
+    Ok(LdltFactorization::new(matrix)?.solve(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_poisson_1d, create_random_spd};
+
+    #[test]
+    fn solves_spd_system() {
+        let matrix = create_random_spd(7, 1e3, 3).unwrap();
+        let x = DVector::from_fn(7, |i, _| (i as f64).sin());
+        let solution = ldlt_solve(&matrix, &(&matrix * &x)).unwrap();
+        assert!((solution - x).norm() < 1e-9);
+    }
+
+    // нулевая диагональ требует блока 2x2
+    #[test]
+    fn solves_indefinite_system_with_zero_diagonal() {
+        let matrix = DMatrix::from_row_slice(3, 3, &[0.0, 1.0, 2.0, 1.0, 0.0, 3.0, 2.0, 3.0, 0.0]);
+        let x = DVector::from_vec(vec![1.0, -1.0, 2.0]);
+        let factorization = LdltFactorization::new(&matrix).unwrap();
+        assert!((factorization.solve(&(&matrix * &x)) - x).norm() < 1e-12);
+
+        let p = DMatrix::from_fn(3, 3, |i, j| if factorization.permutation()[i] == j { 1.0 } else { 0.0 });
+        let l = factorization.l();
+        assert!((l * factorization.d() * l.transpose() - &p * &matrix * p.transpose()).amax() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_zero_matrix() {
+        assert!(matches!(LdltFactorization::new(&DMatrix::zeros(2, 2)), Err(FactorizationError::ZeroPivot { row: 0 })));
+        assert!(ldlt_solve(&create_poisson_1d(0).to_dense(), &DVector::zeros(0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn zero_rhs_gives_zero_solution() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(ldlt_solve(&matrix, &DVector::zeros(2)).unwrap(), DVector::zeros(2));
+    }
+}
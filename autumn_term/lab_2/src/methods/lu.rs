@@ -1,60 +1,308 @@
 use nalgebra::{DMatrix, DVector};
 
-fn lu_decomposition(matrix: &DMatrix<f64>) -> (DMatrix<f64>, DMatrix<f64>) {
-    let n = matrix.nrows();
-    let mut l = DMatrix::identity(n, n); 
-    let mut u = matrix.clone(); 
-
-    for i in 0..n {
-        for j in (i + 1)..n {
-            
-            let factor = u[(j, i)] / u[(i, i)];
-            l[(j, i)] = factor; 
-
-            for k in i..n {
-                u[(j, k)] -= factor * u[(i, k)];
+use super::matrix::SystemMatrix;
+use super::solver::{check_square, FactorizationError, LinearSolver, SolveReport, SolverError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pivoting {
+    // выбор главного элемента по столбцу
+    Partial,
+    // выбор главного элемента по всей оставшейся подматрице
+    Complete,
+}
+
+// P A Q = L U; L (с единичной диагональю) и U хранятся в одной матрице
+#[derive(Debug, Clone)]
+pub struct LuFactorization {
+    lu: DMatrix<f64>,
+    row_permutation: Vec<usize>,
+    col_permutation: Vec<usize>,
+    permutation_sign: f64,
+}
+
+impl LuFactorization {
+    // вырожденность не ошибка разложения: её видно по zero_pivot, а solve возвращает ZeroPivot
+    pub fn new(matrix: &DMatrix<f64>, pivoting: Pivoting) -> Result<Self, FactorizationError> {
+        check_square(matrix)?;
+        let n = matrix.nrows();
+        let mut lu = matrix.clone();
+        let mut row_permutation: Vec<usize> = (0..n).collect();
+        let mut col_permutation: Vec<usize> = (0..n).collect();
+        let mut permutation_sign = 1.0;
+
+        for i in 0..n {
+            let (pivot_row, pivot_col) = match pivoting {
+                Pivoting::Partial => {
+                    let row = (i..n).max_by(|&x, &y| lu[(x, i)].abs().total_cmp(&lu[(y, i)].abs())).unwrap();
+                    (row, i)
+                }
+                Pivoting::Complete => (i..n)
+                    .flat_map(|row| (i..n).map(move |col| (row, col)))
+                    .max_by(|&x, &y| lu[x].abs().total_cmp(&lu[y].abs()))
+                    .unwrap(),
+            };
+
+            if pivot_row != i {
+                lu.swap_rows(i, pivot_row);
+                row_permutation.swap(i, pivot_row);
+                permutation_sign = -permutation_sign;
+            }
+            if pivot_col != i {
+                lu.swap_columns(i, pivot_col);
+                col_permutation.swap(i, pivot_col);
+                permutation_sign = -permutation_sign;
+            }
+
+            // нулевой ведущий элемент: матрица вырождена, исключение пропускаем
+            if lu[(i, i)] == 0.0 {
+                continue;
+            }
+
+            for j in (i + 1)..n {
+                let factor = lu[(j, i)] / lu[(i, i)];
+                lu[(j, i)] = factor;
+
+                for k in (i + 1)..n {
+                    lu[(j, k)] -= factor * lu[(i, k)];
+                }
             }
         }
+
+        Ok(LuFactorization { lu, row_permutation, col_permutation, permutation_sign })
     }
 
-    (l, u)
-}
+    pub fn l(&self) -> DMatrix<f64> {
+        let n = self.lu.nrows();
+        DMatrix::from_fn(n, n, |i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.lu[(i, j)],
+            std::cmp::Ordering::Equal => 1.0,
+            std::cmp::Ordering::Less => 0.0,
+        })
+    }
 
-fn solve_lower_triangular(l: &DMatrix<f64>, b: &DVector<f64>) -> DVector<f64> {
-    let n = l.nrows();
-    let mut y = DVector::zeros(n);
+    pub fn u(&self) -> DMatrix<f64> {
+        self.lu.upper_triangle()
+    }
 
-    for i in 0..n {
-        
-        let sum: f64 = (0..i).map(|k| l[(i, k)] * y[k]).sum();
-        
-        y[i] = b[i] - sum;
+    // i-я строка P A - это строка row_permutation[i] матрицы A
+    pub fn row_permutation(&self) -> &[usize] {
+        &self.row_permutation
     }
 
-    y
-}
+    // i-й столбец A Q - это столбец col_permutation[i] матрицы A
+    pub fn col_permutation(&self) -> &[usize] {
+        &self.col_permutation
+    }
+
+    // первая строка с нулевым ведущим элементом
+    pub fn zero_pivot(&self) -> Option<usize> {
+        self.lu.diagonal().iter().position(|&d| d == 0.0)
+    }
+
+    pub fn is_singular(&self) -> bool {
+        self.zero_pivot().is_some()
+    }
+
+    // вырожденная матрица и правая часть не той длины - ошибки, а не паника
+    fn check_rhs(&self, b: &DVector<f64>) -> Result<(), SolverError> {
+        if let Some(row) = self.zero_pivot() {
+            return Err(SolverError::ZeroPivot { row });
+        }
+        if b.len() != self.lu.nrows() {
+            return Err(SolverError::InvalidParameter { name: "right-hand side length", value: b.len() as f64 });
+        }
+        Ok(())
+    }
+
+    pub fn solve(&self, b: &DVector<f64>) -> Result<DVector<f64>, SolverError> {
+        self.check_rhs(b)?;
+        Ok(self.substitute(b))
+    }
+
+    // решение A^T x = b: A^T = Q U^T L^T P
+    pub fn solve_transpose(&self, b: &DVector<f64>) -> Result<DVector<f64>, SolverError> {
+        self.check_rhs(b)?;
+        Ok(self.substitute_transpose(b))
+    }
+
+    // прямой и обратный ход без проверок: вызывающий уже убедился, что матрица невырождена
+    pub(crate) fn substitute(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.lu.nrows();
+
+        let mut y = DVector::from_fn(n, |i, _| b[self.row_permutation[i]]);
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| self.lu[(i, k)] * y[k]).sum();
+            y[i] -= sum;
+        }
+
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|j| self.lu[(i, j)] * y[j]).sum();
+            y[i] = (y[i] - sum) / self.lu[(i, i)];
+        }
 
-fn solve_upper_triangular(u: &DMatrix<f64>, y: &DVector<f64>) -> DVector<f64> {
-    let n = u.nrows();
-    let mut x = DVector::zeros(n);
+        let mut x = DVector::zeros(n);
+        for (i, &col) in self.col_permutation.iter().enumerate() {
+            x[col] = y[i];
+        }
 
-    for i in (0..n).rev() {
-        let sum: f64 = (i + 1..n).map(|j| u[(i, j)] * x[j]).sum();
-        x[i] = (y[i] - sum) / u[(i, i)];
+        x
     }
 
-    x
+    pub(crate) fn substitute_transpose(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.lu.nrows();
+
+        let mut y = DVector::from_fn(n, |i, _| b[self.col_permutation[i]]);
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| self.lu[(k, i)] * y[k]).sum();
+            y[i] = (y[i] - sum) / self.lu[(i, i)];
+        }
+
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| self.lu[(k, i)] * y[k]).sum();
+            y[i] -= sum;
+        }
+
+        let mut x = DVector::zeros(n);
+        for (i, &row) in self.row_permutation.iter().enumerate() {
+            x[row] = y[i];
+        }
+
+        x
+    }
+
+    // каждый столбец b - отдельная правая часть
+    pub fn solve_many(&self, b: &DMatrix<f64>) -> Result<DMatrix<f64>, FactorizationError> {
+        if let Some(row) = self.zero_pivot() {
+            return Err(FactorizationError::ZeroPivot { row });
+        }
+
+        let mut x = DMatrix::zeros(b.nrows(), b.ncols());
+        for k in 0..b.ncols() {
+            x.set_column(k, &self.substitute(&b.column(k).into_owned()));
+        }
+
+        Ok(x)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.permutation_sign * self.lu.diagonal().product()
+    }
+
+    pub fn inverse(&self) -> Result<DMatrix<f64>, FactorizationError> {
+        let n = self.lu.nrows();
+        self.solve_many(&DMatrix::identity(n, n))
+    }
 }
 
-pub fn lu_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> DVector<f64> {
-    let (l, u) = lu_decomposition(matrix);
+pub struct Lu {
+    pub pivoting: Pivoting,
+}
 
-    //println!("L:\n{}", l);
-    //println!("U:\n{}", u);
+impl Lu {
+    pub fn new(pivoting: Pivoting) -> Self {
+        Lu { pivoting }
+    }
+}
 
-    let y = solve_lower_triangular(&l, b);
-    let x = solve_upper_triangular(&u, &y);
+impl LinearSolver for Lu {
+    fn name(&self) -> &str {
+        "LU"
+    }
 
-    x
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        let solution = LuFactorization::new(&matrix.to_dense(), self.pivoting)
+            .map_err(SolverError::from)
+            .and_then(|factorization| factorization.solve(f));
+        match solution {
+            Ok(solution) => SolveReport::direct(solution),
+            Err(error) => SolveReport::failed(f.len(), error),
+        }
+    }
 }
 
+pub fn lu_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> Result<DVector<f64>, SolverError> {
+    LuFactorization::new(matrix, Pivoting::Partial)?.solve(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_custom_matrix, create_hilbert_matrix, create_vector_f};
+
+    #[test]
+    fn factors_reproduce_permuted_matrix() {
+        let matrix = DMatrix::from_row_slice(3, 3, &[0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 4.0, -1.0, 3.0]);
+
+        for pivoting in [Pivoting::Partial, Pivoting::Complete] {
+            let factorization = LuFactorization::new(&matrix, pivoting).unwrap();
+            let permuted = DMatrix::from_fn(3, 3, |i, j| matrix[(factorization.row_permutation()[i], factorization.col_permutation()[j])]);
+            assert!((factorization.l() * factorization.u() - permuted).norm() < 1e-12);
+            assert!((factorization.determinant() - matrix.determinant()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn solves_and_inverts() {
+        let matrix = create_custom_matrix(20, 10.0);
+        let f = create_vector_f(20);
+        let factorization = LuFactorization::new(&matrix, Pivoting::Partial).unwrap();
+
+        assert!((&matrix * factorization.solve(&f).unwrap() - &f).norm() < 1e-10);
+        assert!((matrix.transpose() * factorization.solve_transpose(&f).unwrap() - &f).norm() < 1e-10);
+        assert!((&matrix * factorization.inverse().unwrap() - DMatrix::identity(20, 20)).norm() < 1e-10);
+    }
+
+    // полный выбор не хуже частичного на плохо обусловленной матрице
+    #[test]
+    fn complete_pivoting_solves_hilbert_system() {
+        let matrix = create_hilbert_matrix(8);
+        let x = DVector::from_element(8, 1.0);
+        let solution = LuFactorization::new(&matrix, Pivoting::Complete).unwrap().solve(&(&matrix * &x)).unwrap();
+        assert!((solution - x).norm() < 1e-5);
+    }
+
+    #[test]
+    fn singular_matrix_is_an_error() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        assert!(matches!(lu_solve(&matrix, &DVector::from_element(2, 1.0)), Err(SolverError::ZeroPivot { row: 1 })));
+
+        let report = Lu::new(Pivoting::Partial).solve(&matrix, &DVector::from_element(2, 1.0));
+        assert!(!report.converged);
+    }
+
+    #[test]
+    fn zero_diagonal_and_empty_system() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(lu_solve(&matrix, &DVector::from_vec(vec![2.0, 3.0])).unwrap(), DVector::from_vec(vec![3.0, 2.0]));
+        assert!(lu_solve(&DMatrix::zeros(0, 0), &DVector::zeros(0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn non_square_matrix_is_an_error() {
+        let matrix = DMatrix::from_element(2, 3, 1.0);
+        assert!(matches!(LuFactorization::new(&matrix, Pivoting::Partial), Err(FactorizationError::NotSquare { rows: 2, cols: 3 })));
+
+        let report = Lu::new(Pivoting::Partial).solve(&matrix, &DVector::from_element(2, 1.0));
+        assert!(matches!(report.error, Some(SolverError::Factorization(FactorizationError::NotSquare { .. }))));
+    }
+
+    #[test]
+    fn singular_factorization_solves_return_errors() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        let factorization = LuFactorization::new(&matrix, Pivoting::Partial).unwrap();
+        let f = DVector::from_element(2, 1.0);
+        assert!(matches!(factorization.solve(&f), Err(SolverError::ZeroPivot { .. })));
+        assert!(matches!(factorization.solve_transpose(&f), Err(SolverError::ZeroPivot { .. })));
+
+        let regular = LuFactorization::new(&DMatrix::identity(2, 2), Pivoting::Partial).unwrap();
+        assert!(matches!(regular.solve(&DVector::zeros(3)), Err(SolverError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let factorization = LuFactorization::new(&DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]), Pivoting::Partial).unwrap();
+        assert!(matches!(factorization.inverse(), Err(FactorizationError::ZeroPivot { row: 1 })));
+        assert!(matches!(factorization.solve_many(&DMatrix::identity(2, 2)), Err(FactorizationError::ZeroPivot { row: 1 })));
+    }
+}
@@ -0,0 +1,49 @@
+use nalgebra::{DMatrix, DVector};
+
+// Sync нужен для параллельного режима (см. parallel.rs)
+pub trait SystemMatrix: Sync {
+    fn nrows(&self) -> usize;
+    fn ncols(&self) -> usize;
+    fn mul_vec(&self, x: &DVector<f64>) -> DVector<f64>;
+    fn tr_mul_vec(&self, x: &DVector<f64>) -> DVector<f64>;
+    // скалярное произведение i-й строки матрицы на вектор x
+    fn row_dot(&self, i: usize, x: &DVector<f64>) -> f64;
+    // ненулевые элементы i-й строки: (столбец, значение)
+    fn row_entries(&self, i: usize) -> Vec<(usize, f64)>;
+    fn diagonal(&self) -> DVector<f64>;
+    fn to_dense(&self) -> DMatrix<f64>;
+}
+
+impl SystemMatrix for DMatrix<f64> {
+    fn nrows(&self) -> usize {
+        self.nrows()
+    }
+
+    fn ncols(&self) -> usize {
+        self.ncols()
+    }
+
+    fn mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        self * x
+    }
+
+    fn tr_mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        self.tr_mul(x)
+    }
+
+    fn row_dot(&self, i: usize, x: &DVector<f64>) -> f64 {
+        (0..self.ncols()).map(|j| self[(i, j)] * x[j]).sum()
+    }
+
+    fn row_entries(&self, i: usize) -> Vec<(usize, f64)> {
+        (0..self.ncols()).map(|j| (j, self[(i, j)])).filter(|&(_, value)| value != 0.0).collect()
+    }
+
+    fn diagonal(&self) -> DVector<f64> {
+        self.diagonal()
+    }
+
+    fn to_dense(&self) -> DMatrix<f64> {
+        self.clone()
+    }
+}
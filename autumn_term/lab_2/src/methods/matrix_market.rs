@@ -0,0 +1,236 @@
+use nalgebra::DVector;
+
+use std::error::Error;
+use std::fs::{File, create_dir_all};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use super::matrix::SystemMatrix;
+use super::sparse::CsrMatrix;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Coordinate,
+    Array,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symmetry {
+    General,
+    Symmetric,
+}
+
+struct Header {
+    format: Format,
+    symmetry: Symmetry,
+}
+
+fn parse_header(line: &str) -> Result<Header, Box<dyn Error>> {
+    let tokens: Vec<String> = line.split_whitespace().map(|t| t.to_lowercase()).collect();
+    if tokens.len() != 5 || tokens[0] != "%%matrixmarket" || tokens[1] != "matrix" {
+        return Err(format!("Invalid Matrix Market header: {}", line).into());
+    }
+
+    let format = match tokens[2].as_str() {
+        "coordinate" => Format::Coordinate,
+        "array" => Format::Array,
+        other => return Err(format!("Unsupported Matrix Market format: {}", other).into()),
+    };
+
+    // integer-файлы читаем как вещественные, complex и pattern не поддерживаем
+    if tokens[3] != "real" && tokens[3] != "integer" {
+        return Err(format!("Unsupported Matrix Market field: {}", tokens[3]).into());
+    }
+
+    let symmetry = match tokens[4].as_str() {
+        "general" => Symmetry::General,
+        "symmetric" => Symmetry::Symmetric,
+        other => return Err(format!("Unsupported Matrix Market symmetry: {}", other).into()),
+    };
+
+    Ok(Header { format, symmetry })
+}
+
+fn parse_number<T: std::str::FromStr>(token: Option<&str>, line: &str) -> Result<T, Box<dyn Error>> {
+    token
+        .and_then(|t| t.parse().ok())
+        .ok_or_else(|| format!("Malformed Matrix Market line: {}", line).into())
+}
+
+pub fn read_matrix_market(path: &Path) -> Result<CsrMatrix, Box<dyn Error>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut lines = reader.lines();
+
+    let header = match lines.next() {
+        Some(line) => parse_header(&line?)?,
+        None => return Err(format!("Empty Matrix Market file: {}", path.display()).into()),
+    };
+
+    let mut data = Vec::new();
+    for line in lines {
+        let line = line?;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('%') {
+            data.push(trimmed.to_string());
+        }
+    }
+
+    let size_line = data.first().ok_or("Matrix Market file has no size line")?;
+    let mut size = size_line.split_whitespace();
+    let nrows: usize = parse_number(size.next(), size_line)?;
+    let ncols: usize = parse_number(size.next(), size_line)?;
+
+    let mut triplets = Vec::new();
+    match header.format {
+        Format::Coordinate => {
+            let nnz: usize = parse_number(size.next(), size_line)?;
+            if data.len() - 1 != nnz {
+                return Err(format!("Expected {} entries, found {}", nnz, data.len() - 1).into());
+            }
+
+            for line in &data[1..] {
+                let mut tokens = line.split_whitespace();
+                let i: usize = parse_number(tokens.next(), line)?;
+                let j: usize = parse_number(tokens.next(), line)?;
+                let value: f64 = parse_number(tokens.next(), line)?;
+                if i == 0 || j == 0 || i > nrows || j > ncols {
+                    return Err(format!("Entry ({}, {}) is out of bounds", i, j).into());
+                }
+                triplets.push((i - 1, j - 1, value));
+            }
+        }
+        Format::Array => {
+            // значения идут по столбцам; для symmetric хранится только нижний треугольник
+            let positions: Vec<(usize, usize)> = match header.symmetry {
+                Symmetry::General => (0..ncols).flat_map(|j| (0..nrows).map(move |i| (i, j))).collect(),
+                Symmetry::Symmetric => (0..ncols).flat_map(|j| (j..nrows).map(move |i| (i, j))).collect(),
+            };
+            if data.len() - 1 != positions.len() {
+                return Err(format!("Expected {} values, found {}", positions.len(), data.len() - 1).into());
+            }
+
+            for (line, &(i, j)) in data[1..].iter().zip(&positions) {
+                let value: f64 = parse_number(line.split_whitespace().next(), line)?;
+                if value != 0.0 {
+                    triplets.push((i, j, value));
+                }
+            }
+        }
+    }
+
+    if header.symmetry == Symmetry::Symmetric {
+        let mirrored: Vec<_> = triplets.iter().filter(|&&(i, j, _)| i != j).map(|&(i, j, value)| (j, i, value)).collect();
+        triplets.extend(mirrored);
+    }
+
+    Ok(CsrMatrix::from_triplets(nrows, ncols, &triplets))
+}
+
+pub fn read_matrix_market_vector(path: &Path) -> Result<DVector<f64>, Box<dyn Error>> {
+    let matrix = read_matrix_market(path)?;
+    if matrix.ncols() != 1 {
+        return Err(format!("Expected a single column in {}, found {}", path.display(), matrix.ncols()).into());
+    }
+
+    Ok(DVector::from_iterator(matrix.nrows(), (0..matrix.nrows()).map(|i| matrix.get(i, 0))))
+}
+
+// без файла правой части берётся f = A * (1, ..., 1), чтобы точное решение было известно
+pub fn read_matrix_market_system(matrix_path: &Path, rhs_path: Option<&Path>) -> Result<(CsrMatrix, DVector<f64>), Box<dyn Error>> {
+    let matrix = read_matrix_market(matrix_path)?;
+    let f = match rhs_path {
+        Some(rhs_path) => read_matrix_market_vector(rhs_path)?,
+        None => matrix.mul_vec(&DVector::from_element(matrix.ncols(), 1.0)),
+    };
+
+    if f.len() != matrix.nrows() {
+        return Err(format!("Right-hand side has {} entries, matrix has {} rows", f.len(), matrix.nrows()).into());
+    }
+
+    Ok((matrix, f))
+}
+
+fn create_writer(path: &Path) -> Result<BufWriter<File>, Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    Ok(BufWriter::new(File::create(path)?))
+}
+
+pub fn write_matrix_market(path: &Path, matrix: &CsrMatrix) -> Result<(), Box<dyn Error>> {
+    let mut writer = create_writer(path)?;
+
+    writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+    writeln!(writer, "{} {} {}", matrix.nrows(), matrix.ncols(), matrix.nnz())?;
+    for (i, j, value) in matrix.triplets() {
+        writeln!(writer, "{} {} {:e}", i + 1, j + 1, value)?;
+    }
+
+    // ошибка записи хвоста буфера иначе потерялась бы в Drop
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn write_matrix_market_vector(path: &Path, vector: &DVector<f64>) -> Result<(), Box<dyn Error>> {
+    let mut writer = create_writer(path)?;
+
+    writeln!(writer, "%%MatrixMarket matrix array real general")?;
+    writeln!(writer, "{} 1", vector.len())?;
+    for value in vector.iter() {
+        writeln!(writer, "{:e}", value)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("lab_2_matrix_market_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn round_trip_keeps_matrix_and_vector() {
+        let matrix = CsrMatrix::from_triplets(3, 2, &[(0, 0, 1.5), (2, 1, -2.25e-3), (1, 0, 7.0)]);
+        let vector = DVector::from_vec(vec![1.0, -0.5, 3.0]);
+        let matrix_path = temp_path("round_trip.mtx");
+        let vector_path = temp_path("round_trip_rhs.mtx");
+
+        write_matrix_market(&matrix_path, &matrix).unwrap();
+        write_matrix_market_vector(&vector_path, &vector).unwrap();
+
+        assert_eq!(read_matrix_market(&matrix_path).unwrap().to_dense(), matrix.to_dense());
+        assert_eq!(read_matrix_market_vector(&vector_path).unwrap(), vector);
+        fs::remove_file(matrix_path).unwrap();
+        fs::remove_file(vector_path).unwrap();
+    }
+
+    #[test]
+    fn symmetric_files_are_mirrored() {
+        let path = temp_path("symmetric.mtx");
+        fs::write(&path, "%%MatrixMarket matrix coordinate real symmetric\n% comment\n2 2 2\n1 1 4\n2 1 1\n").unwrap();
+
+        let (matrix, f) = read_matrix_market_system(&path, None).unwrap();
+        assert_eq!(matrix.get(0, 1), 1.0);
+        assert_eq!(matrix.get(1, 0), 1.0);
+        assert_eq!(f, DVector::from_vec(vec![5.0, 1.0]));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn malformed_files_are_rejected() {
+        let path = temp_path("malformed.mtx");
+        fs::write(&path, "%%MatrixMarket matrix coordinate complex general\n1 1 1\n1 1 1 0\n").unwrap();
+        assert!(read_matrix_market(&path).is_err());
+
+        fs::write(&path, "%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1.0\n").unwrap();
+        assert!(read_matrix_market(&path).is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
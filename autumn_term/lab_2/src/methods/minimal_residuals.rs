@@ -1,26 +1,143 @@
-use nalgebra::{DMatrix, DVector};
-
-pub fn minimal_residuals_solve(matrix: &DMatrix<f64>, f: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
-    let n = matrix.nrows();
-    let mut x = DVector::zeros(n);  
-    let mut r = f - matrix * &x;     
-    let mut residuals = vec![];     
-
-    for iteration in 0..max_iterations {
-        let z = matrix * &r;  
-        let alpha = r.dot(&r) / r.dot(&z);  
-        x += alpha * &r;  
-        r = f - matrix * &x;  
-
-        let residual_norm = r.norm();  
-        residuals.push(residual_norm);
-
-        if residual_norm < tolerance {
-            println!("Minimal residual method converged in {} iterations", iteration + 1);
-            return (x, residuals);
+use nalgebra::DVector;
+
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::preconditioner::{precondition, Preconditioner, PreconditionerSide, Preconditioning};
+use super::solver::{LinearSolver, SolveReport, SolverError, StopReason};
+use super::stopping::StoppingCriterion;
+
+pub struct MinimalResiduals {
+    pub criterion: StoppingCriterion,
+    pub preconditioning: Option<Preconditioning>,
+}
+
+impl MinimalResiduals {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        MinimalResiduals { criterion: StoppingCriterion::absolute_residual(tolerance, max_iterations), preconditioning: None }
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    pub fn with_preconditioner(mut self, preconditioner: Box<dyn Preconditioner>, side: PreconditionerSide) -> Self {
+        self.preconditioning = Some(Preconditioning { preconditioner, side });
+        self
+    }
+}
+
+impl LinearSolver for MinimalResiduals {
+    fn name(&self) -> &str {
+        "Minimal residual"
+    }
+
+    // та же схема предобуславливания, что и в BiCGSTAB
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, f, &mut ())
+    }
+
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let preconditioning = self.preconditioning.as_ref();
+        let operator = |v: &DVector<f64>| {
+            let w = matrix.mul_vec(&precondition(preconditioning, PreconditionerSide::Right, v.clone()));
+            precondition(preconditioning, PreconditionerSide::Left, w)
+        };
+        let f = &precondition(preconditioning, PreconditionerSide::Left, f.clone());
+
+        let n = matrix.nrows();
+        let mut x = DVector::zeros(n);  
+        let mut r = f - operator(&x);     
+        let mut monitor = self.criterion.monitor(f).with_observer(observer);
+
+        // при r = 0 шаг alpha = (r, r) / (r, A r) не определён
+        if monitor.initially_converged(r.norm()) {
+            monitor.announce("Minimal residual method", 0, StopReason::Converged);
+            return monitor.finish(precondition(preconditioning, PreconditionerSide::Right, x), 0, StopReason::Converged);
+        }
+
+        for iteration in 0..monitor.max_iterations() {
+            let z = operator(&r);  
+            let curvature = r.dot(&z);
+            // (r, A r) = 0: шаг не определён, A + A^T не является положительно определённой
+            if curvature.abs() < 1e-50 {
+                return monitor.fail(precondition(preconditioning, PreconditionerSide::Right, x), iteration + 1,
+                    SolverError::Indefinite { iteration: iteration + 1, curvature });
+            }
+            let alpha = r.dot(&r) / curvature;  
+            let residual_step_norm = r.norm();
+            x += alpha * &r;  
+            r = f - operator(&x);  
+
+            let residual_norm = r.norm();  
+
+            if let Some(stop_reason) = monitor.check(residual_norm, Some(alpha.abs() * residual_step_norm)) {
+                monitor.announce("Minimal residual method", iteration + 1, stop_reason);
+                return monitor.finish(precondition(preconditioning, PreconditionerSide::Right, x), iteration + 1, stop_reason);
+            }
+        }
+
+        let max_iterations = monitor.max_iterations();
+        monitor.announce("Minimal residual method", max_iterations, StopReason::MaxIterations);
+        monitor.finish(precondition(preconditioning, PreconditionerSide::Right, x), max_iterations, StopReason::MaxIterations)
+    }
+}
+
+pub fn minimal_residuals_solve(matrix: &dyn SystemMatrix, f: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = MinimalResiduals::new(tolerance, max_iterations).solve(matrix, f);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_vector_f};
+    use crate::methods::preconditioner::JacobiPreconditioner;
+
+    #[test]
+    fn solves_banded_system() {
+        let matrix = create_custom_sparse_matrix(40, 10.0);
+        let f = create_vector_f(40);
+
+        for side in [None, Some(PreconditionerSide::Left), Some(PreconditionerSide::Right)] {
+            let mut solver = MinimalResiduals::new(1e-10, 1000);
+            if let Some(side) = side {
+                solver = solver.with_preconditioner(Box::new(JacobiPreconditioner::new(&matrix).unwrap()), side);
+            }
+            let report = solver.solve(&matrix, &f);
+
+            assert!(report.converged, "{:?}", side);
+            assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-8);
         }
     }
 
-    println!("Warning!!! Minimal residual method did not converge in {} iterations.", max_iterations);
-    (x, residuals)
-}
\ No newline at end of file
+    #[test]
+    fn zero_rhs_converges_immediately() {
+        let solver = MinimalResiduals::new(1e-10, 100).with_criterion(StoppingCriterion::relative_residual(1e-10, 100));
+        let report = solver.solve(&create_custom_sparse_matrix(10, 10.0), &DVector::zeros(10));
+
+        assert_eq!(report.stop_reason, StopReason::Converged);
+        assert_eq!(report.solution, DVector::zeros(10));
+    }
+
+    #[test]
+    fn empty_system_converges_immediately() {
+        let report = MinimalResiduals::new(1e-10, 100).solve(&DMatrix::zeros(0, 0), &DVector::zeros(0));
+
+        assert!(report.converged);
+        assert!(report.solution.is_empty());
+    }
+
+    // при нулевой диагонали (r, A r) может обратиться в ноль уже на первом шаге
+    #[test]
+    fn zero_diagonal_breakdown_is_reported() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let report = MinimalResiduals::new(1e-10, 50).solve(&matrix, &DVector::from_vec(vec![1.0, 0.0]));
+
+        assert_eq!(report.stop_reason, StopReason::Breakdown);
+        assert!(matches!(report.error, Some(SolverError::Indefinite { iteration: 1, .. })));
+        assert!(report.solution.iter().all(|value| value.is_finite()));
+    }
+}
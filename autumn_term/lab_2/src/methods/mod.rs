@@ -1,10 +1,35 @@
+pub mod solver;
+pub mod stopping;
+pub mod observer;
+pub mod matrix;
+pub mod sparse;
+pub mod band;
+pub mod matrix_market;
+pub mod preconditioner;
+pub mod conditioning;
+pub mod diagnostics;
+pub mod parallel;
+pub mod ordering;
+
 pub mod gauss_pivot;
 pub mod lu;
+pub mod refinement;
+pub mod cholesky;
+pub mod ldlt;
+pub mod qr;
+pub mod banded;
+pub mod tridiagonal;
+pub mod generators;
 pub mod jacobi;
 pub mod seidel;
 pub mod upper_relaxation;
 pub mod gradient_descent;
 pub mod minimal_residuals;
+pub mod conjugate_gradient;
 pub mod bicgstab;
+pub mod gmres;
+pub mod multigrid;
+pub mod eigen;
 
-pub mod plotting;
\ No newline at end of file
+pub mod plotting;
+pub mod chart;
\ No newline at end of file
@@ -0,0 +1,363 @@
+use nalgebra::DVector;
+
+use super::generators::{create_poisson_1d, create_poisson_2d};
+use super::jacobi::jacobi_sweep;
+use super::lu::{LuFactorization, Pivoting};
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::ordering::{color_classes, relaxation_sweep, Ordering, Sweep};
+use super::parallel::Execution;
+use super::solver::{LinearSolver, SolveReport, SolverError, StopReason};
+use super::sparse::CsrMatrix;
+use super::stopping::StoppingCriterion;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cycle {
+    V,
+    W,
+    // полный многосеточный метод: решение на грубой сетке интерполируется вверх и служит начальным
+    // приближением, дальше V-циклы
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Smoother {
+    // взвешенный Якоби, для Пуассона обычно weight = 2/3 (1D) или 4/5 (2D)
+    Jacobi(f64),
+    Seidel { ordering: Ordering, sweep: Sweep },
+}
+
+struct Level {
+    matrix: CsrMatrix,
+    // интерполяция со следующей, более грубой сетки на эту; у самой грубой сетки её нет
+    prolongation: Option<CsrMatrix>,
+    colors: Option<Vec<Vec<usize>>>,
+}
+
+// сетки с n = 2^k - 1 внутренними узлами по каждому направлению, матрицы без множителя 1 / h^2
+// (как в generators), поэтому при переходе на грубую сетку правая часть умножается на 4
+pub struct Multigrid {
+    levels: Vec<Level>,
+    coarsest: LuFactorization,
+    // отношение полного взвешивания к P^T: 1/2 в 1D, 1/4 в 2D
+    restriction_scale: f64,
+    pub cycle: Cycle,
+    pub smoother: Smoother,
+    pub pre_smoothing: usize,
+    pub post_smoothing: usize,
+    pub criterion: StoppingCriterion,
+}
+
+// узел i мелкой сетки: нечётный совпадает с грубым узлом (i - 1) / 2, чётный лежит посередине
+fn interpolation_weights(i: usize, coarse_size: usize) -> Vec<(usize, f64)> {
+    if i % 2 == 1 {
+        return vec![((i - 1) / 2, 1.0)];
+    }
+
+    let mut weights = Vec::with_capacity(2);
+    if i > 0 {
+        weights.push((i / 2 - 1, 0.5));
+    }
+    if i / 2 < coarse_size {
+        weights.push((i / 2, 0.5));
+    }
+    weights
+}
+
+fn prolongation_1d(coarse_size: usize) -> CsrMatrix {
+    let fine_size = 2 * coarse_size + 1;
+    let mut triplets = Vec::new();
+    for i in 0..fine_size {
+        for (j, weight) in interpolation_weights(i, coarse_size) {
+            triplets.push((i, j, weight));
+        }
+    }
+
+    CsrMatrix::from_triplets(fine_size, coarse_size, &triplets)
+}
+
+// билинейная интерполяция - тензорное произведение одномерных весов
+fn prolongation_2d(coarse_size: usize) -> CsrMatrix {
+    let fine_size = 2 * coarse_size + 1;
+    let mut triplets = Vec::new();
+    for i in 0..fine_size {
+        for j in 0..fine_size {
+            for (ci, wi) in interpolation_weights(i, coarse_size) {
+                for (cj, wj) in interpolation_weights(j, coarse_size) {
+                    triplets.push((i * fine_size + j, ci * coarse_size + cj, wi * wj));
+                }
+            }
+        }
+    }
+
+    CsrMatrix::from_triplets(fine_size * fine_size, coarse_size * coarse_size, &triplets)
+}
+
+fn is_grid_size(n: usize) -> bool {
+    (n + 1).is_power_of_two() && n >= 1
+}
+
+// самая грубая сетка решается прямым методом
+const COARSEST_SIZE: usize = 3;
+
+impl Multigrid {
+    // n = 2^k - 1 узлов, иначе InvalidParameter
+    pub fn poisson_1d(n: usize, tolerance: f64, max_iterations: usize) -> Result<Self, SolverError> {
+        if !is_grid_size(n) {
+            return Err(SolverError::InvalidParameter { name: "grid size (needs 2^k - 1)", value: n as f64 });
+        }
+        let mut sizes = vec![n];
+        while *sizes.last().unwrap() > COARSEST_SIZE {
+            sizes.push((sizes.last().unwrap() - 1) / 2);
+        }
+
+        let levels = sizes.iter().enumerate().map(|(k, &size)| Level {
+            matrix: create_poisson_1d(size),
+            prolongation: sizes.get(k + 1).map(|&coarse| prolongation_1d(coarse)),
+            colors: None,
+        }).collect();
+
+        Multigrid::from_levels(levels, 0.5, Smoother::Jacobi(2.0 / 3.0), tolerance, max_iterations)
+    }
+
+    // сетка m x m, n = m^2 неизвестных
+    pub fn poisson_2d(m: usize, tolerance: f64, max_iterations: usize) -> Result<Self, SolverError> {
+        if !is_grid_size(m) {
+            return Err(SolverError::InvalidParameter { name: "grid size (needs 2^k - 1)", value: m as f64 });
+        }
+        let mut sizes = vec![m];
+        while *sizes.last().unwrap() > COARSEST_SIZE {
+            sizes.push((sizes.last().unwrap() - 1) / 2);
+        }
+
+        let levels = sizes.iter().enumerate().map(|(k, &size)| Level {
+            matrix: create_poisson_2d(size),
+            prolongation: sizes.get(k + 1).map(|&coarse| prolongation_2d(coarse)),
+            colors: None,
+        }).collect();
+
+        Multigrid::from_levels(levels, 0.25, Smoother::Jacobi(0.8), tolerance, max_iterations)
+    }
+
+    fn from_levels(levels: Vec<Level>, restriction_scale: f64, smoother: Smoother, tolerance: f64, max_iterations: usize) -> Result<Self, SolverError> {
+        let coarsest = LuFactorization::new(&levels.last().unwrap().matrix.to_dense(), Pivoting::Partial)?;
+        if let Some(row) = coarsest.zero_pivot() {
+            return Err(SolverError::ZeroPivot { row });
+        }
+        Ok(Multigrid {
+            levels,
+            coarsest,
+            restriction_scale,
+            cycle: Cycle::V,
+            smoother,
+            pre_smoothing: 2,
+            post_smoothing: 2,
+            criterion: StoppingCriterion::relative_residual(tolerance, max_iterations),
+        })
+    }
+
+    pub fn with_cycle(mut self, cycle: Cycle) -> Self {
+        self.cycle = cycle;
+        self
+    }
+
+    // раскраска для красно-чёрного Зейделя считается один раз на каждом уровне
+    pub fn with_smoother(mut self, smoother: Smoother) -> Result<Self, SolverError> {
+        self.smoother = smoother;
+        for level in &mut self.levels {
+            level.colors = match smoother {
+                Smoother::Seidel { ordering, .. } => color_classes(&level.matrix, ordering)?,
+                Smoother::Jacobi(_) => None,
+            };
+        }
+        Ok(self)
+    }
+
+    pub fn with_smoothing(mut self, pre_smoothing: usize, post_smoothing: usize) -> Self {
+        self.pre_smoothing = pre_smoothing;
+        self.post_smoothing = post_smoothing;
+        self
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    fn smooth(&self, level: usize, b: &DVector<f64>, x: &mut DVector<f64>, steps: usize) {
+        let matrix = &self.levels[level].matrix;
+        for _ in 0..steps {
+            match self.smoother {
+                Smoother::Jacobi(weight) => *x = jacobi_sweep(matrix, b, x, weight, Execution::Serial),
+                Smoother::Seidel { sweep, .. } => {
+                    relaxation_sweep(matrix, b, x, 1.0, self.levels[level].colors.as_deref(), sweep, Execution::Serial)
+                }
+            }
+        }
+    }
+
+    // правая часть задачи для ошибки на следующей сетке: 4 * (полное взвешивание r)
+    fn restrict(&self, level: usize, r: &DVector<f64>) -> DVector<f64> {
+        let prolongation = self.levels[level].prolongation.as_ref().unwrap();
+        4.0 * self.restriction_scale * prolongation.tr_mul_vec(r)
+    }
+
+    fn prolongate(&self, level: usize, coarse: &DVector<f64>) -> DVector<f64> {
+        self.levels[level].prolongation.as_ref().unwrap().mul_vec(coarse)
+    }
+
+    // gamma = 1 - V-цикл, gamma = 2 - W-цикл
+    fn cycle(&self, level: usize, b: &DVector<f64>, x: &mut DVector<f64>, gamma: usize) {
+        if level + 1 == self.levels.len() {
+            *x = self.coarsest.substitute(b);
+            return;
+        }
+
+        self.smooth(level, b, x, self.pre_smoothing);
+
+        let residual = b - self.levels[level].matrix.mul_vec(x);
+        let coarse_b = self.restrict(level, &residual);
+        let mut coarse_x = DVector::zeros(coarse_b.len());
+        for _ in 0..gamma {
+            self.cycle(level + 1, &coarse_b, &mut coarse_x, gamma);
+        }
+        *x += self.prolongate(level, &coarse_x);
+
+        self.smooth(level, b, x, self.post_smoothing);
+    }
+
+    // правая часть спускается на самую грубую сетку, затем решение поднимается с одним V-циклом на уровень
+    fn full_multigrid(&self, f: &DVector<f64>) -> DVector<f64> {
+        let mut right_hand_sides = vec![f.clone()];
+        for level in 0..self.levels.len() - 1 {
+            let coarse = self.restrict(level, right_hand_sides.last().unwrap());
+            right_hand_sides.push(coarse);
+        }
+
+        let mut x = self.coarsest.substitute(right_hand_sides.last().unwrap());
+        for level in (0..self.levels.len() - 1).rev() {
+            x = self.prolongate(level, &x);
+            self.cycle(level, &right_hand_sides[level], &mut x, 1);
+        }
+
+        x
+    }
+}
+
+impl LinearSolver for Multigrid {
+    fn name(&self) -> &str {
+        match self.cycle {
+            Cycle::V => "Multigrid V-cycle",
+            Cycle::W => "Multigrid W-cycle",
+            Cycle::Full => "Full multigrid",
+        }
+    }
+
+    // матрица должна совпадать с матрицей самого мелкого уровня, иерархия строится заранее
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, f, &mut ())
+    }
+
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let n = self.levels[0].matrix.nrows();
+        if matrix.nrows() != n || f.len() != n {
+            let report = SolveReport::failed(f.len(), SolverError::InvalidParameter { name: "system size (must match the grid)", value: matrix.nrows() as f64 });
+            observer.on_finish(&report);
+            return report;
+        }
+
+        let mut x = DVector::zeros(f.len());
+        let mut monitor = self.criterion.monitor(f).with_observer(observer);
+        let gamma = if self.cycle == Cycle::W { 2 } else { 1 };
+
+        for iteration in 0..monitor.max_iterations() {
+            let x_old = x.clone();
+            if iteration == 0 && self.cycle == Cycle::Full {
+                x = self.full_multigrid(f);
+            } else {
+                self.cycle(0, f, &mut x, gamma);
+            }
+
+            let residual = f - matrix.mul_vec(&x);
+            if let Some(stop_reason) = monitor.check(residual.norm(), Some((&x - &x_old).norm())) {
+                monitor.announce(self.name(), iteration + 1, stop_reason);
+                return monitor.finish(x, iteration + 1, stop_reason);
+            }
+        }
+
+        let max_iterations = monitor.max_iterations();
+        monitor.announce(self.name(), max_iterations, StopReason::MaxIterations);
+        monitor.finish(x, max_iterations, StopReason::MaxIterations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::create_vector_f;
+
+    #[test]
+    fn every_cycle_solves_poisson_1d() {
+        let matrix = create_poisson_1d(63);
+        let f = create_vector_f(63);
+
+        for cycle in [Cycle::V, Cycle::W, Cycle::Full] {
+            let report = Multigrid::poisson_1d(63, 1e-10, 100).unwrap().with_cycle(cycle).solve(&matrix, &f);
+            assert!(report.converged, "{:?}", cycle);
+            assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-9 * f.norm());
+        }
+    }
+
+    // число V-циклов почти не зависит от размера сетки
+    #[test]
+    fn iterations_do_not_grow_with_grid() {
+        let iterations = |m: usize| {
+            let report = Multigrid::poisson_2d(m, 1e-8, 100).unwrap().solve(&create_poisson_2d(m), &create_vector_f(m * m));
+            assert!(report.converged);
+            report.iterations
+        };
+
+        assert_eq!(Multigrid::poisson_2d(31, 1e-8, 100).unwrap().level_count(), 4);
+        assert!(iterations(31) <= iterations(7) + 2);
+    }
+
+    #[test]
+    fn red_black_seidel_smoother() {
+        let smoother = Smoother::Seidel { ordering: Ordering::RedBlack, sweep: Sweep::Symmetric };
+        let solver = Multigrid::poisson_2d(15, 1e-10, 100).unwrap().with_smoother(smoother).unwrap().with_smoothing(1, 1);
+        let f = create_vector_f(225);
+        let report = solver.solve(&create_poisson_2d(15), &f);
+
+        assert!(report.converged);
+        assert!(report.iterations < 20);
+    }
+
+    // самая грубая сетка решается сразу
+    #[test]
+    fn coarsest_grid_only_and_zero_rhs() {
+        let solver = Multigrid::poisson_1d(3, 1e-12, 10).unwrap();
+        assert_eq!(solver.level_count(), 1);
+        assert_eq!(solver.solve(&create_poisson_1d(3), &create_vector_f(3)).iterations, 1);
+
+        let report = Multigrid::poisson_1d(15, 1e-12, 10).unwrap().solve(&create_poisson_1d(15), &DVector::zeros(15));
+        assert!(report.converged);
+        assert_eq!(report.solution, DVector::zeros(15));
+    }
+
+    #[test]
+    fn wrong_grid_and_system_sizes_are_errors() {
+        for n in [0, 4, 6] {
+            assert!(matches!(Multigrid::poisson_1d(n, 1e-10, 10), Err(SolverError::InvalidParameter { .. })), "{}", n);
+            assert!(matches!(Multigrid::poisson_2d(n, 1e-10, 10), Err(SolverError::InvalidParameter { .. })), "{}", n);
+        }
+
+        let report = Multigrid::poisson_1d(7, 1e-10, 10).unwrap().solve(&create_poisson_1d(15), &create_vector_f(15));
+        assert!(!report.converged);
+        assert!(matches!(report.error, Some(SolverError::InvalidParameter { .. })));
+    }
+}
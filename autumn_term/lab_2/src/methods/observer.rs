@@ -0,0 +1,450 @@
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufWriter, Stderr, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::solver::{SolveReport, SolverError};
+
+// одна запись истории невязок; iteration - номер записи с нуля, как в save_residuals_to_csv
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IterationEvent {
+    pub iteration: usize,
+    pub residual: f64,
+    // норма шага известна не во всех методах
+    pub step_norm: Option<f64>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObserverAction {
+    Continue,
+    // метод останавливается с StopReason::Interrupted
+    Stop,
+}
+
+// вызывается на каждой итерации из StoppingMonitor::check и один раз в конце решения
+pub trait IterationObserver {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction;
+
+    fn on_finish(&mut self, _report: &SolveReport) {}
+}
+
+// решение без наблюдателя
+impl IterationObserver for () {
+    fn on_iteration(&mut self, _event: &IterationEvent) -> ObserverAction {
+        ObserverAction::Continue
+    }
+}
+
+impl<T: IterationObserver + ?Sized> IterationObserver for &mut T {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction {
+        (**self).on_iteration(event)
+    }
+
+    fn on_finish(&mut self, report: &SolveReport) {
+        (**self).on_finish(report)
+    }
+}
+
+impl<T: IterationObserver> IterationObserver for Option<T> {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction {
+        match self {
+            Some(observer) => observer.on_iteration(event),
+            None => ObserverAction::Continue,
+        }
+    }
+
+    fn on_finish(&mut self, report: &SolveReport) {
+        if let Some(observer) = self {
+            observer.on_finish(report);
+        }
+    }
+}
+
+// несколько наблюдателей сразу; остановка, если её запросил хотя бы один
+#[derive(Default)]
+pub struct Observers<'a> {
+    observers: Vec<&'a mut dyn IterationObserver>,
+}
+
+impl<'a> Observers<'a> {
+    pub fn new() -> Self {
+        Observers { observers: Vec::new() }
+    }
+
+    pub fn with(mut self, observer: &'a mut dyn IterationObserver) -> Self {
+        self.observers.push(observer);
+        self
+    }
+}
+
+impl IterationObserver for Observers<'_> {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction {
+        let mut action = ObserverAction::Continue;
+        for observer in self.observers.iter_mut() {
+            if observer.on_iteration(event) == ObserverAction::Stop {
+                action = ObserverAction::Stop;
+            }
+        }
+        action
+    }
+
+    fn on_finish(&mut self, report: &SolveReport) {
+        for observer in self.observers.iter_mut() {
+            observer.on_finish(report);
+        }
+    }
+}
+
+fn create_file(path: &Path) -> Result<BufWriter<File>, SolverError> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    Ok(BufWriter::new(File::create(path)?))
+}
+
+// по умолчанию "iteration,residual" - тот же формат, что у save_residuals_to_csv;
+// with_details добавляет норму шага и время в секундах.
+// ошибка записи запоминается, дальнейшие записи пропускаются, а сама ошибка возвращается из finish
+pub struct CsvObserver<W: Write> {
+    writer: W,
+    details: bool,
+    header_written: bool,
+    error: Option<io::Error>,
+}
+
+impl CsvObserver<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, SolverError> {
+        Ok(CsvObserver::new(create_file(path.as_ref())?))
+    }
+}
+
+impl<W: Write> CsvObserver<W> {
+    pub fn new(writer: W) -> Self {
+        CsvObserver { writer, details: false, header_written: false, error: None }
+    }
+
+    pub fn with_details(mut self) -> Self {
+        self.details = true;
+        self
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        self.header_written = true;
+        let header = if self.details { "iteration,residual,step_norm,elapsed" } else { "iteration,residual" };
+        writeln!(self.writer, "{}", header)
+    }
+
+    fn write_event(&mut self, event: &IterationEvent) -> io::Result<()> {
+        if !self.header_written {
+            self.write_header()?;
+        }
+
+        if self.details {
+            let step_norm = event.step_norm.map(|step| step.to_string()).unwrap_or_default();
+            writeln!(self.writer, "{},{},{},{}", event.iteration, event.residual, step_norm, event.elapsed.as_secs_f64())
+        } else {
+            writeln!(self.writer, "{},{}", event.iteration, event.residual)
+        }
+    }
+
+    // пустая история всё равно даёт файл с заголовком
+    pub fn finish(mut self) -> Result<W, SolverError> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+        if !self.header_written {
+            self.write_header()?;
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> IterationObserver for CsvObserver<W> {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction {
+        if self.error.is_none() {
+            if let Err(error) = self.write_event(event) {
+                self.error = Some(error);
+            }
+        }
+        ObserverAction::Continue
+    }
+}
+
+// одна JSON-запись на итерацию и итоговая запись в конце; NaN и inf записываются как null
+pub struct JsonLinesObserver<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+fn json_number(value: f64) -> String {
+    if value.is_finite() { format!("{:e}", value) } else { "null".to_string() }
+}
+
+impl JsonLinesObserver<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, SolverError> {
+        Ok(JsonLinesObserver::new(create_file(path.as_ref())?))
+    }
+}
+
+impl<W: Write> JsonLinesObserver<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLinesObserver { writer, error: None }
+    }
+
+    fn record(&mut self, line: String) {
+        if self.error.is_none() {
+            if let Err(error) = writeln!(self.writer, "{}", line) {
+                self.error = Some(error);
+            }
+        }
+    }
+
+    pub fn finish(mut self) -> Result<W, SolverError> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> IterationObserver for JsonLinesObserver<W> {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction {
+        let step_norm = event.step_norm.map(json_number).unwrap_or_else(|| "null".to_string());
+        self.record(format!("{{\"iteration\":{},\"residual\":{},\"step_norm\":{},\"elapsed\":{:e}}}",
+            event.iteration, json_number(event.residual), step_norm, event.elapsed.as_secs_f64()));
+        ObserverAction::Continue
+    }
+
+    fn on_finish(&mut self, report: &SolveReport) {
+        let error = match &report.error {
+            Some(error) => format!("\"{}\"", error.to_string().replace('\\', "\\\\").replace('"', "\\\"")),
+            None => "null".to_string(),
+        };
+        self.record(format!("{{\"iterations\":{},\"converged\":{},\"stop_reason\":\"{:?}\",\"error\":{}}}",
+            report.iterations, report.converged, report.stop_reason, error));
+    }
+}
+
+// строка прогресса в stderr, перерисовывается не чаще раза в interval;
+// с with_target заполнение считается по log(r_0 / r) / log(r_0 / target), иначе по числу итераций
+pub struct ProgressBar {
+    max_iterations: usize,
+    target: Option<f64>,
+    width: usize,
+    interval: Duration,
+    initial_residual: Option<f64>,
+    last_draw: Option<Instant>,
+    last_event: Option<IterationEvent>,
+    output: Stderr,
+}
+
+impl ProgressBar {
+    pub fn new(max_iterations: usize) -> Self {
+        ProgressBar {
+            max_iterations,
+            target: None,
+            width: 30,
+            interval: Duration::from_millis(100),
+            initial_residual: None,
+            last_draw: None,
+            last_event: None,
+            output: io::stderr(),
+        }
+    }
+
+    pub fn with_target(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    fn fraction(&self, event: &IterationEvent) -> f64 {
+        let by_iterations = (event.iteration + 1) as f64 / self.max_iterations.max(1) as f64;
+        let by_residual = match (self.target, self.initial_residual) {
+            (Some(target), Some(initial)) if initial > target && event.residual > 0.0 => {
+                (initial / event.residual).ln() / (initial / target).ln()
+            }
+            _ => 0.0,
+        };
+        by_iterations.max(by_residual).clamp(0.0, 1.0)
+    }
+
+    // ошибки вывода в терминал игнорируются: прогресс не должен прерывать решение
+    fn draw(&mut self, event: &IterationEvent) {
+        let filled = (self.fraction(event) * self.width as f64).round() as usize;
+        let _ = write!(self.output, "\r[{}{}] iteration {}/{}, residual {:e}, {:.1} s",
+            "#".repeat(filled), " ".repeat(self.width - filled), event.iteration + 1, self.max_iterations,
+            event.residual, event.elapsed.as_secs_f64());
+        let _ = self.output.flush();
+        self.last_draw = Some(Instant::now());
+    }
+}
+
+impl IterationObserver for ProgressBar {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction {
+        self.initial_residual.get_or_insert(event.residual);
+        self.last_event = Some(*event);
+        if self.last_draw.is_none_or(|drawn| drawn.elapsed() >= self.interval) {
+            self.draw(event);
+        }
+        ObserverAction::Continue
+    }
+
+    fn on_finish(&mut self, report: &SolveReport) {
+        if let Some(event) = self.last_event {
+            self.draw(&event);
+            let _ = writeln!(self.output, " - {:?}", report.stop_reason);
+        }
+    }
+}
+
+// остановка по условию пользователя, например, по внешнему флагу или порогу невязки
+pub struct EarlyStop<F: FnMut(&IterationEvent) -> bool> {
+    condition: F,
+}
+
+impl<F: FnMut(&IterationEvent) -> bool> EarlyStop<F> {
+    pub fn new(condition: F) -> Self {
+        EarlyStop { condition }
+    }
+}
+
+impl<F: FnMut(&IterationEvent) -> bool> IterationObserver for EarlyStop<F> {
+    fn on_iteration(&mut self, event: &IterationEvent) -> ObserverAction {
+        if (self.condition)(event) { ObserverAction::Stop } else { ObserverAction::Continue }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_vector_f};
+    use crate::methods::jacobi::Jacobi;
+    use crate::methods::solver::{LinearSolver, StopReason};
+
+    // запись всегда завершается ошибкой
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn event(iteration: usize, residual: f64, step_norm: Option<f64>) -> IterationEvent {
+        IterationEvent { iteration, residual, step_norm, elapsed: Duration::from_millis(500) }
+    }
+
+    #[test]
+    fn csv_observer_writes_residual_history() {
+        let mut observer = CsvObserver::new(Vec::new());
+        observer.on_iteration(&event(0, 1.0, None));
+        observer.on_iteration(&event(1, 0.5, Some(0.25)));
+
+        let output = String::from_utf8(observer.finish().unwrap()).unwrap();
+        assert_eq!(output, "iteration,residual\n0,1\n1,0.5\n");
+    }
+
+    #[test]
+    fn csv_observer_with_details_leaves_unknown_step_empty() {
+        let mut observer = CsvObserver::new(Vec::new()).with_details();
+        observer.on_iteration(&event(0, 1.0, None));
+        observer.on_iteration(&event(1, 0.5, Some(0.25)));
+
+        let output = String::from_utf8(observer.finish().unwrap()).unwrap();
+        assert_eq!(output, "iteration,residual,step_norm,elapsed\n0,1,,0.5\n1,0.5,0.25,0.5\n");
+    }
+
+    #[test]
+    fn empty_history_gives_header_only() {
+        let output = CsvObserver::new(Vec::new()).finish().unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "iteration,residual\n");
+
+        let output = JsonLinesObserver::new(Vec::new()).finish().unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn write_errors_are_returned_from_finish() {
+        let mut csv = CsvObserver::new(FailingWriter);
+        assert_eq!(csv.on_iteration(&event(0, 1.0, None)), ObserverAction::Continue);
+        assert!(matches!(csv.finish(), Err(SolverError::Io(_))));
+
+        let mut json = JsonLinesObserver::new(FailingWriter);
+        json.on_iteration(&event(0, 1.0, None));
+        assert!(matches!(json.finish(), Err(SolverError::Io(_))));
+    }
+
+    #[test]
+    fn json_lines_write_null_for_non_finite_values_and_final_record() {
+        let mut observer = JsonLinesObserver::new(Vec::new());
+        observer.on_iteration(&event(0, f64::NAN, Some(f64::INFINITY)));
+        observer.on_finish(&SolveReport::failed(2, SolverError::ZeroPivot { row: 1 }));
+
+        let output = String::from_utf8(observer.finish().unwrap()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "{\"iteration\":0,\"residual\":null,\"step_norm\":null,\"elapsed\":5e-1}");
+        assert_eq!(lines[1], "{\"iterations\":0,\"converged\":false,\"stop_reason\":\"Breakdown\",\"error\":\"zero pivot at row 1\"}");
+    }
+
+    #[test]
+    fn observers_stop_when_any_of_them_asks() {
+        let mut csv = CsvObserver::new(Vec::new());
+        let mut stop = EarlyStop::new(|event: &IterationEvent| event.iteration == 1);
+        let mut missing: Option<CsvObserver<Vec<u8>>> = None;
+        let mut observers = Observers::new().with(&mut csv).with(&mut stop).with(&mut missing);
+
+        assert_eq!(observers.on_iteration(&event(0, 1.0, None)), ObserverAction::Continue);
+        assert_eq!(observers.on_iteration(&event(1, 0.5, None)), ObserverAction::Stop);
+
+        // остановивший наблюдатель не мешает остальным получить событие
+        let output = String::from_utf8(csv.finish().unwrap()).unwrap();
+        assert_eq!(output.lines().count(), 3);
+    }
+
+    #[test]
+    fn observed_solve_records_every_residual() {
+        let matrix = create_custom_sparse_matrix(20, 10.0);
+        let mut observer = CsvObserver::new(Vec::new());
+        let report = Jacobi::new(1e-10, 1000).solve_observed(&matrix, &create_vector_f(20), &mut observer);
+
+        assert!(report.converged);
+        let output = String::from_utf8(observer.finish().unwrap()).unwrap();
+        assert_eq!(output.lines().count(), report.residuals.len() + 1);
+    }
+
+    #[test]
+    fn early_stop_interrupts_solver() {
+        let matrix = create_custom_sparse_matrix(20, 10.0);
+        let mut stop = EarlyStop::new(|event: &IterationEvent| event.iteration == 2);
+        let report = Jacobi::new(1e-10, 1000).solve_observed(&matrix, &create_vector_f(20), &mut stop);
+
+        assert_eq!(report.stop_reason, StopReason::Interrupted);
+        assert!(!report.converged);
+    }
+
+    // заполнение - наибольшее из долей по итерациям и по снижению невязки
+    #[test]
+    fn progress_fraction_uses_iterations_and_residual_target() {
+        let by_iterations = ProgressBar::new(10);
+        assert_eq!(by_iterations.fraction(&event(4, 1.0, None)), 0.5);
+
+        let mut by_residual = ProgressBar::new(1000).with_target(1e-6);
+        by_residual.initial_residual = Some(1.0);
+        assert!((by_residual.fraction(&event(0, 1e-3, None)) - 0.5).abs() < 1e-12);
+        assert_eq!(by_residual.fraction(&event(0, 1e-9, None)), 1.0);
+
+        // при нулевом числе итераций деления на ноль нет
+        assert_eq!(ProgressBar::new(0).fraction(&event(0, 1.0, None)), 1.0);
+    }
+}
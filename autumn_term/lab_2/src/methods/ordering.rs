@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use nalgebra::DVector;
+
+use super::matrix::SystemMatrix;
+use super::parallel::{map_indices, Execution};
+use super::solver::SolverError;
+
+// порядок обхода неизвестных в методе Зейделя
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ordering {
+    #[default]
+    Natural,
+    // сначала все "красные" неизвестные, затем все "чёрные"; внутри цвета обновления независимы
+    RedBlack,
+    // жадная раскраска графа: подходит для любой разреженности, цветов обычно немного больше двух
+    Multicolor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sweep {
+    #[default]
+    Forward,
+    // прямой проход, затем обратный: симметричный Зейдель (SGS) и SSOR
+    Symmetric,
+}
+
+// раскраска графа смежности матрицы в два цвета обходом в ширину;
+// None, если граф не двудольный (например, у пятидиагональной матрицы пункта д));
+// рассчитано на структурно симметричные матрицы
+pub fn red_black_coloring(matrix: &dyn SystemMatrix) -> Option<Vec<Vec<usize>>> {
+    let n = matrix.nrows();
+    let mut color: Vec<Option<usize>> = vec![None; n];
+    let mut queue = VecDeque::new();
+
+    for start in 0..n {
+        if color[start].is_some() {
+            continue;
+        }
+        color[start] = Some(0);
+        queue.push_back(start);
+
+        while let Some(i) = queue.pop_front() {
+            let current = color[i].unwrap();
+            for (j, _) in matrix.row_entries(i) {
+                if j == i {
+                    continue;
+                }
+                match color[j] {
+                    None => {
+                        color[j] = Some(1 - current);
+                        queue.push_back(j);
+                    }
+                    Some(c) if c == current => return None,
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    let mut classes = vec![Vec::new(), Vec::new()];
+    for (i, c) in color.into_iter().enumerate() {
+        classes[c.unwrap()].push(i);
+    }
+
+    Some(classes)
+}
+
+// каждой вершине - наименьший цвет, не занятый уже раскрашенными соседями;
+// рассчитано на структурно симметричные матрицы
+pub fn greedy_coloring(matrix: &dyn SystemMatrix) -> Vec<Vec<usize>> {
+    let n = matrix.nrows();
+    let mut color: Vec<Option<usize>> = vec![None; n];
+    let mut classes: Vec<Vec<usize>> = Vec::new();
+
+    for i in 0..n {
+        let neighbour_colors: Vec<usize> = matrix.row_entries(i).iter().filter(|&&(j, _)| j != i).filter_map(|&(j, _)| color[j]).collect();
+        let c = (0..).find(|c| !neighbour_colors.contains(c)).unwrap();
+        color[i] = Some(c);
+        if c == classes.len() {
+            classes.push(Vec::new());
+        }
+        classes[c].push(i);
+    }
+
+    classes
+}
+
+// None - естественный порядок; красно-чёрный порядок для недвудольного графа - ошибка
+pub fn color_classes(matrix: &dyn SystemMatrix, ordering: Ordering) -> Result<Option<Vec<Vec<usize>>>, SolverError> {
+    match ordering {
+        Ordering::Natural => Ok(None),
+        Ordering::RedBlack => red_black_coloring(matrix).map(Some).ok_or(SolverError::NotBipartite),
+        Ordering::Multicolor => Ok(Some(greedy_coloring(matrix))),
+    }
+}
+
+// один шаг релаксации на месте: x_i <- (1 - omega) x_i + omega (b_i - sum_{j != i} a_ij x_j) / a_ii;
+// при omega = 1 это шаг Зейделя
+pub fn relaxation_sweep(matrix: &dyn SystemMatrix, b: &DVector<f64>, x: &mut DVector<f64>, omega: f64,
+    colors: Option<&[Vec<usize>]>, sweep: Sweep, execution: Execution) {
+    let diagonal = matrix.diagonal();
+    let update = |i: usize, x: &DVector<f64>| {
+        let sum = matrix.row_dot(i, x) - diagonal[i] * x[i];
+        (1.0 - omega) * x[i] + (omega * (b[i] - sum)) / diagonal[i]
+    };
+
+    let passes: &[bool] = match sweep {
+        Sweep::Forward => &[false],
+        Sweep::Symmetric => &[false, true],
+    };
+
+    for &backward in passes {
+        match colors {
+            None => {
+                let n = matrix.nrows();
+                // компоненты, пройденные раньше, уже обновлены, остальные ещё старые
+                for k in 0..n {
+                    let i = if backward { n - 1 - k } else { k };
+                    x[i] = update(i, x);
+                }
+            }
+            Some(colors) => {
+                // неизвестные одного цвета не связаны между собой, их можно считать одновременно
+                let order: Vec<&Vec<usize>> = if backward { colors.iter().rev().collect() } else { colors.iter().collect() };
+                for color in order {
+                    let values = map_indices(execution, color, |i| update(i, x));
+                    for (&i, value) in color.iter().zip(values) {
+                        x[i] = value;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_poisson_2d, create_vector_f};
+
+    // внутри каждого цвета нет связей между неизвестными
+    fn is_proper_coloring(matrix: &dyn SystemMatrix, classes: &[Vec<usize>]) -> bool {
+        classes.iter().all(|class| class.iter().all(|&i| matrix.row_entries(i).iter().all(|&(j, _)| j == i || !class.contains(&j))))
+    }
+
+    #[test]
+    fn poisson_2d_is_red_black() {
+        let matrix = create_poisson_2d(5);
+        let classes = red_black_coloring(&matrix).unwrap();
+
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].len() + classes[1].len(), 25);
+        assert!(is_proper_coloring(&matrix, &classes));
+    }
+
+    #[test]
+    fn pentadiagonal_matrix_needs_more_colors() {
+        let matrix = create_custom_sparse_matrix(10, 10.0);
+        let classes = greedy_coloring(&matrix);
+
+        assert!(red_black_coloring(&matrix).is_none());
+        assert!(matches!(color_classes(&matrix, Ordering::RedBlack), Err(SolverError::NotBipartite)));
+        assert_eq!(classes.len(), 3);
+        assert!(is_proper_coloring(&matrix, &classes));
+    }
+
+    // для двух цветов порядок внутри цвета не важен, результат совпадает с обычным обходом в порядке "красные, затем чёрные"
+    #[test]
+    fn colored_sweep_matches_permuted_natural_sweep() {
+        let matrix = create_poisson_2d(4);
+        let f = create_vector_f(16);
+        let classes = color_classes(&matrix, Ordering::RedBlack).unwrap().unwrap();
+
+        let mut colored = DVector::zeros(16);
+        relaxation_sweep(&matrix, &f, &mut colored, 1.2, Some(&classes), Sweep::Forward, Execution::Serial);
+
+        let mut expected = DVector::zeros(16);
+        let diagonal = matrix.diagonal();
+        for &i in classes.iter().flatten() {
+            let sum = matrix.row_dot(i, &expected) - diagonal[i] * expected[i];
+            expected[i] = -0.2 * expected[i] + 1.2 * (f[i] - sum) / diagonal[i];
+        }
+
+        assert!((colored - expected).norm() < 1e-12);
+    }
+
+    #[test]
+    fn natural_ordering_and_empty_matrix() {
+        assert!(color_classes(&create_poisson_2d(3), Ordering::Natural).unwrap().is_none());
+        assert!(greedy_coloring(&create_poisson_2d(0)).is_empty());
+        assert_eq!(red_black_coloring(&create_poisson_2d(0)), Some(vec![Vec::new(), Vec::new()]));
+    }
+}
@@ -0,0 +1,100 @@
+use nalgebra::DVector;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::matrix::SystemMatrix;
+
+// Parallel доступен только с feature "parallel"; без неё всё считается последовательно
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Execution {
+    #[default]
+    Serial,
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+// куски для параллельного скалярного произведения: меньше - накладные расходы rayon дороже самой работы
+#[cfg(feature = "parallel")]
+const DOT_CHUNK: usize = 4096;
+
+// вектор из значений row(0), ..., row(n - 1)
+pub fn map_rows(execution: Execution, n: usize, row: impl Fn(usize) -> f64 + Sync + Send) -> DVector<f64> {
+    match execution {
+        Execution::Serial => DVector::from_iterator(n, (0..n).map(row)),
+        #[cfg(feature = "parallel")]
+        Execution::Parallel => DVector::from_vec((0..n).into_par_iter().map(row).collect()),
+    }
+}
+
+// значения row(i) для заданных индексов, в том же порядке
+pub fn map_indices(execution: Execution, indices: &[usize], row: impl Fn(usize) -> f64 + Sync + Send) -> Vec<f64> {
+    match execution {
+        Execution::Serial => indices.iter().map(|&i| row(i)).collect(),
+        #[cfg(feature = "parallel")]
+        Execution::Parallel => indices.par_iter().map(|&i| row(i)).collect(),
+    }
+}
+
+pub fn mul_vec(execution: Execution, matrix: &dyn SystemMatrix, x: &DVector<f64>) -> DVector<f64> {
+    match execution {
+        Execution::Serial => matrix.mul_vec(x),
+        #[cfg(feature = "parallel")]
+        Execution::Parallel => map_rows(execution, matrix.nrows(), |i| matrix.row_dot(i, x)),
+    }
+}
+
+pub fn dot(execution: Execution, a: &DVector<f64>, b: &DVector<f64>) -> f64 {
+    match execution {
+        Execution::Serial => a.dot(b),
+        #[cfg(feature = "parallel")]
+        Execution::Parallel => a
+            .as_slice()
+            .par_chunks(DOT_CHUNK)
+            .zip(b.as_slice().par_chunks(DOT_CHUNK))
+            .map(|(a, b)| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>())
+            .sum(),
+    }
+}
+
+pub fn norm(execution: Execution, v: &DVector<f64>) -> f64 {
+    dot(execution, v, v).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_vector_f};
+
+    #[test]
+    fn serial_operations_match_nalgebra() {
+        let matrix = create_custom_sparse_matrix(20, 10.0);
+        let x = create_vector_f(20);
+
+        assert_eq!(mul_vec(Execution::Serial, &matrix, &x), matrix.mul_vec(&x));
+        assert_eq!(map_rows(Execution::Serial, 20, |i| matrix.row_dot(i, &x)), matrix.mul_vec(&x));
+        assert_eq!(map_indices(Execution::Serial, &[3, 1], |i| x[i]), vec![4.0, 2.0]);
+        assert_eq!(norm(Execution::Serial, &x), x.norm());
+    }
+
+    #[test]
+    fn empty_vectors() {
+        assert!(map_rows(Execution::default(), 0, |_| 1.0).is_empty());
+        assert_eq!(dot(Execution::default(), &DVector::zeros(0), &DVector::zeros(0)), 0.0);
+    }
+
+    // суммирование по кускам меняет порядок сложений, поэтому сравнение с допуском
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_operations_match_serial() {
+        let n = 3 * DOT_CHUNK + 17;
+        let matrix = create_custom_sparse_matrix(n, 10.0);
+        let x = create_vector_f(n);
+
+        assert_eq!(mul_vec(Execution::Parallel, &matrix, &x), mul_vec(Execution::Serial, &matrix, &x));
+        assert_eq!(map_indices(Execution::Parallel, &[5, 0, 2], |i| x[i]), map_indices(Execution::Serial, &[5, 0, 2], |i| x[i]));
+        let serial = dot(Execution::Serial, &x, &x);
+        assert!((dot(Execution::Parallel, &x, &x) - serial).abs() < 1e-12 * serial);
+    }
+}
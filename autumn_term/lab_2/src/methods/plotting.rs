@@ -1,19 +1,85 @@
 use std::fs::{File, create_dir_all};
 use std::io::{Write, BufWriter};
 use std::path::Path;
+use std::time::Duration;
+
+use super::observer::{CsvObserver, IterationEvent, IterationObserver};
+use super::ordering::{Ordering, Sweep};
+use super::solver::SolverError;
+
+// формат совпадает с тем, что пишет CsvObserver во время решения
+pub fn save_residuals_to_csv(residuals: &[f64], filename: &str) -> Result<(), SolverError> {
+    let mut observer = CsvObserver::create(filename)?;
+    for (iteration, &residual) in residuals.iter().enumerate() {
+        observer.on_iteration(&IterationEvent { iteration, residual, step_norm: None, elapsed: Duration::ZERO });
+    }
+
+    observer.finish()?;
+    Ok(())
+}
+
+// невязки с предобуславливателем и без него в одном файле; более короткая история дополняется пустыми ячейками
+pub fn save_preconditioned_residuals_to_csv(residuals: &[f64], unpreconditioned_residuals: &[f64], filename: &str) -> Result<(), SolverError> {
 
-pub fn save_residuals_to_csv(residuals: &[f64], filename: &str) {
-    
     let path = Path::new(filename);
     if let Some(parent) = path.parent() {
-        create_dir_all(parent).expect("Failed to create directories");
+        create_dir_all(parent)?;
     }
 
-    let file = File::create(filename).expect("Unable to create file");
+    let file = File::create(filename)?;
     let mut writer = BufWriter::new(file);
 
-    writeln!(writer, "iteration,residual").expect("Unable to write header");
-    for (iteration, residual) in residuals.iter().enumerate() {
-        writeln!(writer, "{},{}", iteration, residual).expect("Unable to write data");
+    let cell = |values: &[f64], iteration: usize| values.get(iteration).map(|v| v.to_string()).unwrap_or_default();
+
+    writeln!(writer, "iteration,residual,unpreconditioned_residual")?;
+    for iteration in 0..residuals.len().max(unpreconditioned_residuals.len()) {
+        writeln!(writer, "{},{},{}", iteration, cell(residuals, iteration), cell(unpreconditioned_residuals, iteration))?;
     }
-}
\ No newline at end of file
+
+    writer.flush()?;
+    Ok(())
+}
+
+
+// общая часть всех таблиц: каталог, заголовок и по строке на элемент
+pub fn save_rows_to_csv<T>(rows: &[T], header: &str, filename: &str, format_row: impl Fn(&T) -> String) -> Result<(), SolverError> {
+
+    let path = Path::new(filename);
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    let file = File::create(filename)?;
+    let mut writer = BufWriter::new(file);
+
+    writeln!(writer, "{}", header)?;
+    for row in rows {
+        writeln!(writer, "{}", format_row(row))?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn save_omega_sweep_to_csv(sweep: &[(f64, usize, bool)], filename: &str) -> Result<(), SolverError> {
+    save_rows_to_csv(sweep, "omega,iterations,converged", filename, |(omega, iterations, converged)| {
+        format!("{},{},{}", omega, iterations, converged)
+    })
+}
+
+// один прогон Зейделя или SOR в исследовании порядка обхода
+#[derive(Debug, Clone)]
+pub struct OrderingStudyRow {
+    pub matrix: String,
+    pub method: String,
+    pub ordering: Ordering,
+    pub sweep: Sweep,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+pub fn save_ordering_study_to_csv(rows: &[OrderingStudyRow], filename: &str) -> Result<(), SolverError> {
+    save_rows_to_csv(rows, "matrix,method,ordering,sweep,iterations,converged", filename, |row| {
+        format!("{},{},{:?},{:?},{},{}", row.matrix, row.method, row.ordering, row.sweep, row.iterations, row.converged)
+    })
+}
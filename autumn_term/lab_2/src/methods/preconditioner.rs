@@ -0,0 +1,407 @@
+use nalgebra::DVector;
+
+use super::matrix::SystemMatrix;
+use super::solver::{FactorizationError, SolverError};
+use super::sparse::CsrMatrix;
+
+pub trait Preconditioner {
+    fn name(&self) -> &str;
+    // возвращает z = M^{-1} r
+    fn apply(&self, r: &DVector<f64>) -> DVector<f64>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreconditionerSide {
+    Left,
+    Right,
+}
+
+pub struct Preconditioning {
+    pub preconditioner: Box<dyn Preconditioner>,
+    pub side: PreconditionerSide,
+}
+
+// применяет предобуславливатель, только если он стоит с нужной стороны
+pub fn precondition(preconditioning: Option<&Preconditioning>, side: PreconditionerSide, v: DVector<f64>) -> DVector<f64> {
+    match preconditioning {
+        Some(p) if p.side == side => p.preconditioner.apply(&v),
+        _ => v,
+    }
+}
+
+fn split_rows(matrix: &CsrMatrix) -> Vec<Vec<(usize, f64)>> {
+    (0..matrix.nrows()).map(|i| matrix.row(i).collect()).collect()
+}
+
+// отсутствующий в шаблоне диагональный элемент - тот же нулевой ведущий элемент
+fn diagonal_position(row: &[(usize, f64)], i: usize) -> Result<usize, SolverError> {
+    row.iter().position(|&(j, _)| j == i).ok_or(SolverError::ZeroPivot { row: i })
+}
+
+pub struct JacobiPreconditioner {
+    inverse_diagonal: DVector<f64>,
+}
+
+impl JacobiPreconditioner {
+    pub fn new(matrix: &dyn SystemMatrix) -> Result<Self, SolverError> {
+        let diagonal = matrix.diagonal();
+        if let Some(row) = diagonal.iter().position(|&d| d == 0.0) {
+            return Err(SolverError::ZeroPivot { row });
+        }
+        Ok(JacobiPreconditioner { inverse_diagonal: diagonal.map(|d| 1.0 / d) })
+    }
+}
+
+impl Preconditioner for JacobiPreconditioner {
+    fn name(&self) -> &str {
+        "Jacobi"
+    }
+
+    fn apply(&self, r: &DVector<f64>) -> DVector<f64> {
+        r.component_mul(&self.inverse_diagonal)
+    }
+}
+
+// стандартный SSOR: M = omega / (2 - omega) * (D / omega + L) D^{-1} (D / omega + U)
+//                    = (D + omega L) D^{-1} (D + omega U) / (omega (2 - omega))
+pub struct Ssor {
+    rows: Vec<Vec<(usize, f64)>>,
+    diagonal: DVector<f64>,
+    omega: f64,
+}
+
+impl Ssor {
+    pub fn new(matrix: &CsrMatrix, omega: f64) -> Result<Self, SolverError> {
+        if !(omega > 0.0 && omega < 2.0) {
+            return Err(SolverError::InvalidParameter { name: "omega", value: omega });
+        }
+        let diagonal = matrix.diagonal();
+        if let Some(row) = diagonal.iter().position(|&d| d == 0.0) {
+            return Err(SolverError::ZeroPivot { row });
+        }
+        Ok(Ssor { rows: split_rows(matrix), diagonal, omega })
+    }
+}
+
+impl Preconditioner for Ssor {
+    fn name(&self) -> &str {
+        "SSOR"
+    }
+
+    fn apply(&self, r: &DVector<f64>) -> DVector<f64> {
+        let n = r.len();
+        let omega = self.omega;
+
+        let mut y = DVector::zeros(n);
+        for i in 0..n {
+            let sum: f64 = self.rows[i].iter().filter(|&&(j, _)| j < i).map(|&(j, value)| value * y[j]).sum();
+            y[i] = (r[i] - sum) * omega / self.diagonal[i];
+        }
+
+        let mut z = DVector::zeros(n);
+        for i in (0..n).rev() {
+            let sum: f64 = self.rows[i].iter().filter(|&&(j, _)| j > i).map(|&(j, value)| value * z[j]).sum();
+            z[i] = (self.diagonal[i] * y[i] - sum) * omega / self.diagonal[i];
+        }
+
+        z * ((2.0 - omega) / omega)
+    }
+}
+
+// неполное LU-разложение без заполнения: L и U хранятся на шаблоне исходной матрицы
+pub struct Ilu0 {
+    rows: Vec<Vec<(usize, f64)>>,
+    diagonal_positions: Vec<usize>,
+}
+
+impl Ilu0 {
+    pub fn new(matrix: &CsrMatrix) -> Result<Self, SolverError> {
+        let n = matrix.nrows();
+        let mut rows = split_rows(matrix);
+        let diagonal_positions = (0..n).map(|i| diagonal_position(&rows[i], i)).collect::<Result<Vec<usize>, _>>()?;
+
+        for i in 0..n {
+            for position in 0..diagonal_positions[i] {
+                let k = rows[i][position].0;
+                let pivot = rows[k][diagonal_positions[k]].1;
+
+                let factor = rows[i][position].1 / pivot;
+                rows[i][position].1 = factor;
+
+                for index in position + 1..rows[i].len() {
+                    let j = rows[i][index].0;
+                    if let Ok(k_index) = rows[k].binary_search_by_key(&j, |&(column, _)| column) {
+                        rows[i][index].1 -= factor * rows[k][k_index].1;
+                    }
+                }
+            }
+
+            // строки выше уже проверены, поэтому делить на pivot выше безопасно
+            if rows[i][diagonal_positions[i]].1 == 0.0 {
+                return Err(SolverError::ZeroPivot { row: i });
+            }
+        }
+
+        Ok(Ilu0 { rows, diagonal_positions })
+    }
+}
+
+impl Preconditioner for Ilu0 {
+    fn name(&self) -> &str {
+        "ILU(0)"
+    }
+
+    fn apply(&self, r: &DVector<f64>) -> DVector<f64> {
+        let n = r.len();
+
+        let mut y = r.clone();
+        for i in 0..n {
+            let row = &self.rows[i][..self.diagonal_positions[i]];
+            y[i] -= row.iter().map(|&(j, value)| value * y[j]).sum::<f64>();
+        }
+
+        let mut z = y;
+        for i in (0..n).rev() {
+            let diagonal = self.diagonal_positions[i];
+            let row = &self.rows[i][diagonal + 1..];
+            let sum: f64 = row.iter().map(|&(j, value)| value * z[j]).sum();
+            z[i] = (z[i] - sum) / self.rows[i][diagonal].1;
+        }
+
+        z
+    }
+}
+
+// неполное разложение Холецкого IC(0): A ≈ L L^T на шаблоне нижнего треугольника A.
+// IC(0) может сорваться и на SPD-матрице; тогда раскладывается A + shift * diag(A)
+// со сдвигом Мантойфеля, который удваивается до успеха
+pub struct IncompleteCholesky {
+    lower_rows: Vec<Vec<(usize, f64)>>,
+    shift: f64,
+}
+
+const INITIAL_SHIFT: f64 = 1e-3;
+const MAX_SHIFT_ATTEMPTS: usize = 30;
+
+impl IncompleteCholesky {
+    // Err, если разложение не удалось даже с наибольшим сдвигом
+    pub fn new(matrix: &CsrMatrix) -> Result<Self, SolverError> {
+        let mut shift = 0.0;
+        let mut result = Self::factorize(matrix, shift);
+        for _ in 0..MAX_SHIFT_ATTEMPTS {
+            // без диагонального элемента сдвиг не поможет
+            if !matches!(result, Err(FactorizationError::NotPositiveDefinite { .. })) {
+                break;
+            }
+            shift = if shift == 0.0 { INITIAL_SHIFT } else { 2.0 * shift };
+            result = Self::factorize(matrix, shift);
+        }
+
+        Ok(IncompleteCholesky { lower_rows: result?, shift })
+    }
+
+    // сдвиг, с которым разложение удалось; 0 - разложена сама матрица
+    pub fn shift(&self) -> f64 {
+        self.shift
+    }
+
+    // NotPositiveDefinite - под корнем получилось неположительное число
+    fn factorize(matrix: &CsrMatrix, shift: f64) -> Result<Vec<Vec<(usize, f64)>>, FactorizationError> {
+        let n = matrix.nrows();
+        let mut lower_rows: Vec<Vec<(usize, f64)>> = Vec::with_capacity(n);
+
+        for i in 0..n {
+            let mut row: Vec<(usize, f64)> = matrix.row(i).filter(|&(j, _)| j <= i).collect();
+            if row.last().map(|&(j, _)| j) != Some(i) {
+                return Err(FactorizationError::ZeroPivot { row: i });
+            }
+            row.last_mut().unwrap().1 *= 1.0 + shift;
+
+            for index in 0..row.len() {
+                let j = row[index].0;
+                let other: &[(usize, f64)] = if j == i { &row[..index] } else { &lower_rows[j] };
+
+                let mut sum = 0.0;
+                let (mut a, mut b) = (0, 0);
+                while a < index && b < other.len() {
+                    let (column_a, column_b) = (row[a].0, other[b].0);
+                    if column_b >= j {
+                        break;
+                    }
+                    if column_a == column_b {
+                        sum += row[a].1 * other[b].1;
+                        a += 1;
+                        b += 1;
+                    } else if column_a < column_b {
+                        a += 1;
+                    } else {
+                        b += 1;
+                    }
+                }
+
+                let value = row[index].1 - sum;
+                if j == i {
+                    if value <= 0.0 || !value.is_finite() {
+                        return Err(FactorizationError::NotPositiveDefinite { row: i, pivot: value });
+                    }
+                    row[index].1 = value.sqrt();
+                } else {
+                    row[index].1 = value / lower_rows[j].last().unwrap().1;
+                }
+            }
+
+            lower_rows.push(row);
+        }
+
+        Ok(lower_rows)
+    }
+}
+
+impl Preconditioner for IncompleteCholesky {
+    fn name(&self) -> &str {
+        "IC(0)"
+    }
+
+    fn apply(&self, r: &DVector<f64>) -> DVector<f64> {
+        let n = r.len();
+
+        let mut y = r.clone();
+        for i in 0..n {
+            let (&(_, diagonal), off_diagonal) = self.lower_rows[i].split_last().unwrap();
+            y[i] = (y[i] - off_diagonal.iter().map(|&(j, value)| value * y[j]).sum::<f64>()) / diagonal;
+        }
+
+        let mut z = y;
+        for i in (0..n).rev() {
+            let (&(_, diagonal), off_diagonal) = self.lower_rows[i].split_last().unwrap();
+            z[i] /= diagonal;
+            for &(j, value) in off_diagonal {
+                z[j] -= value * z[i];
+            }
+        }
+
+        z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_poisson_1d, create_vector_f};
+
+    // на трёхдиагональной матрице неполные разложения не теряют заполнения и точны
+    #[test]
+    fn incomplete_factorizations_are_exact_on_tridiagonal_matrix() {
+        let matrix = create_poisson_1d(20);
+        let f = create_vector_f(20);
+
+        for preconditioner in [Box::new(Ilu0::new(&matrix).unwrap()) as Box<dyn Preconditioner>, Box::new(IncompleteCholesky::new(&matrix).unwrap())] {
+            let z = preconditioner.apply(&f);
+            assert!((matrix.mul_vec(&z) - &f).norm() < 1e-10, "{} is not exact", preconditioner.name());
+        }
+    }
+
+    #[test]
+    fn jacobi_inverts_the_diagonal() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[2.0, 1.0, 1.0, 4.0]);
+        let z = JacobiPreconditioner::new(&matrix).unwrap().apply(&DVector::from_vec(vec![1.0, 1.0]));
+        assert_eq!(z, DVector::from_vec(vec![0.5, 0.25]));
+    }
+
+    #[test]
+    fn ssor_with_unit_omega_is_symmetric_gauss_seidel() {
+        // M = (D + L) D^{-1} (D + U)
+        let dense = DMatrix::from_row_slice(3, 3, &[4.0, -1.0, 0.0, -1.0, 4.0, -1.0, 0.0, -1.0, 4.0]);
+        let lower = dense.lower_triangle();
+        let upper = dense.upper_triangle();
+        let m = &lower * DMatrix::from_diagonal(&dense.diagonal().map(|d| 1.0 / d)) * &upper;
+
+        let r = DVector::from_vec(vec![1.0, 2.0, 3.0]);
+        let z = Ssor::new(&CsrMatrix::from_dense(&dense), 1.0).unwrap().apply(&r);
+        assert!((m * z - r).norm() < 1e-12);
+    }
+
+    #[test]
+    fn ssor_matches_the_standard_formula() {
+        let dense = DMatrix::from_row_slice(3, 3, &[4.0, -1.0, 0.0, -1.0, 4.0, -1.0, 0.0, -1.0, 4.0]);
+        let omega = 1.5;
+        let diagonal = DMatrix::from_diagonal(&dense.diagonal());
+        let lower = &diagonal + (dense.lower_triangle() - &diagonal) * omega;
+        let upper = &diagonal + (dense.upper_triangle() - &diagonal) * omega;
+        let m = lower * DMatrix::from_diagonal(&dense.diagonal().map(|d| 1.0 / d)) * upper / (omega * (2.0 - omega));
+
+        let r = DVector::from_vec(vec![1.0, 2.0, 3.0]);
+        let z = Ssor::new(&CsrMatrix::from_dense(&dense), omega).unwrap().apply(&r);
+        assert!((m * z - r).norm() < 1e-12);
+    }
+
+    #[test]
+    fn ssor_rejects_bad_omega_and_zero_diagonal() {
+        let matrix = create_poisson_1d(4);
+        assert!(matches!(Ssor::new(&matrix, 2.0), Err(SolverError::InvalidParameter { name: "omega", .. })));
+
+        let zero_diagonal = CsrMatrix::from_dense(&DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]));
+        assert!(matches!(Ssor::new(&zero_diagonal, 1.0), Err(SolverError::ZeroPivot { row: 0 })));
+    }
+
+    // матрица Кершоу: SPD, но IC(0) без сдвига срывается
+    #[test]
+    fn incomplete_cholesky_shifts_on_breakdown() {
+        let dense = DMatrix::from_row_slice(4, 4, &[
+            3.0, -2.0, 0.0, 2.0,
+            -2.0, 3.0, -2.0, 0.0,
+            0.0, -2.0, 3.0, -2.0,
+            2.0, 0.0, -2.0, 3.0,
+        ]);
+        assert!(dense.clone().cholesky().is_some());
+
+        let preconditioner = IncompleteCholesky::new(&CsrMatrix::from_dense(&dense)).unwrap();
+        assert!(preconditioner.shift() > 0.0);
+
+        let r = DVector::from_vec(vec![1.0, 0.0, 0.0, 1.0]);
+        let z = preconditioner.apply(&r);
+        assert!(z.iter().all(|value| value.is_finite()));
+        assert!(r.dot(&z) > 0.0);
+    }
+
+    #[test]
+    fn incomplete_cholesky_keeps_zero_shift_when_possible() {
+        assert_eq!(IncompleteCholesky::new(&create_poisson_1d(10)).unwrap().shift(), 0.0);
+    }
+
+    #[test]
+    fn zero_diagonal_is_an_error_for_every_preconditioner() {
+        let dense = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let sparse = CsrMatrix::from_dense(&dense);
+
+        assert!(matches!(JacobiPreconditioner::new(&dense), Err(SolverError::ZeroPivot { row: 0 })));
+        assert!(matches!(Ilu0::new(&sparse), Err(SolverError::ZeroPivot { row: 0 })));
+        assert!(matches!(IncompleteCholesky::new(&sparse), Err(SolverError::ZeroPivot { row: 0 })));
+    }
+
+    // ненулевая диагональ, но ведущий элемент обнуляется при исключении
+    #[test]
+    fn ilu0_reports_zero_pivot_after_elimination() {
+        let sparse = CsrMatrix::from_dense(&DMatrix::from_row_slice(2, 2, &[1.0, 1.0, 1.0, 1.0]));
+        assert!(matches!(Ilu0::new(&sparse), Err(SolverError::ZeroPivot { row: 1 })));
+    }
+
+    // сдвиг диагонали не помогает, если она отрицательна
+    #[test]
+    fn incomplete_cholesky_fails_on_negative_definite_matrix() {
+        let negative = CsrMatrix::from_dense(&(-create_poisson_1d(3).to_dense()));
+        let result = IncompleteCholesky::new(&negative);
+        assert!(matches!(result, Err(SolverError::Factorization(FactorizationError::NotPositiveDefinite { row: 0, .. }))));
+    }
+
+    #[test]
+    fn empty_matrix_gives_empty_preconditioners() {
+        let sparse = create_poisson_1d(0);
+        assert!(JacobiPreconditioner::new(&sparse).unwrap().apply(&DVector::zeros(0)).is_empty());
+        assert!(Ssor::new(&sparse, 1.0).unwrap().apply(&DVector::zeros(0)).is_empty());
+        assert!(Ilu0::new(&sparse).unwrap().apply(&DVector::zeros(0)).is_empty());
+        assert!(IncompleteCholesky::new(&sparse).unwrap().apply(&DVector::zeros(0)).is_empty());
+    }
+}
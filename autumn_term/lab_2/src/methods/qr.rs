@@ -0,0 +1,152 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::matrix::SystemMatrix;
+use super::solver::{FactorizationError, LinearSolver, SolveReport};
+
+// A = Q R отражениями Хаусхолдера; для m > n solve даёт решение задачи наименьших квадратов
+#[derive(Debug, Clone)]
+pub struct QrFactorization {
+    r: DMatrix<f64>,
+    reflectors: Vec<DVector<f64>>,
+}
+
+impl QrFactorization {
+    pub fn new(matrix: &DMatrix<f64>) -> Result<Self, FactorizationError> {
+        let (m, n) = matrix.shape();
+        if m < n {
+            return Err(FactorizationError::Underdetermined { rows: m, cols: n });
+        }
+
+        let mut r = matrix.clone();
+        let mut reflectors = Vec::with_capacity(n);
+        let rank_tolerance = f64::EPSILON * m as f64 * matrix.amax();
+
+        for k in 0..n {
+            let x = r.view((k, k), (m - k, 1)).column(0).into_owned();
+            let alpha = if x[0] >= 0.0 { -x.norm() } else { x.norm() };
+            if alpha.abs() <= rank_tolerance {
+                return Err(FactorizationError::RankDeficient { column: k });
+            }
+
+            let mut v = x;
+            v[0] -= alpha;
+            let v_norm = v.norm();
+            if v_norm > 0.0 {
+                v /= v_norm;
+            }
+
+            let mut block = r.view_mut((k, k), (m - k, n - k));
+            let projection = v.transpose() * &block;
+            block -= 2.0 * &v * projection;
+
+            reflectors.push(v);
+        }
+
+        Ok(QrFactorization { r, reflectors })
+    }
+
+    pub fn r(&self) -> DMatrix<f64> {
+        let n = self.r.ncols();
+        self.r.rows(0, n).upper_triangle()
+    }
+
+    // Q^T b
+    fn apply_qt(&self, b: &DVector<f64>) -> DVector<f64> {
+        let m = self.r.nrows();
+        let mut y = b.clone();
+        for (k, v) in self.reflectors.iter().enumerate() {
+            let mut tail = y.rows_mut(k, m - k);
+            let projection = v.dot(&tail);
+            tail -= 2.0 * projection * v;
+        }
+        y
+    }
+
+    // экономная Q размера m x n
+    pub fn q(&self) -> DMatrix<f64> {
+        let (m, n) = self.r.shape();
+        let mut q = DMatrix::identity(m, n);
+        for (k, v) in self.reflectors.iter().enumerate().rev() {
+            let mut block = q.view_mut((k, 0), (m - k, n));
+            let projection = v.transpose() * &block;
+            block -= 2.0 * v * projection;
+        }
+        q
+    }
+
+    pub fn solve(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.r.ncols();
+        let mut x = self.apply_qt(b).rows(0, n).into_owned();
+
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|j| self.r[(i, j)] * x[j]).sum();
+            x[i] = (x[i] - sum) / self.r[(i, i)];
+        }
+
+        x
+    }
+}
+
+pub struct Qr;
+
+impl LinearSolver for Qr {
+    fn name(&self) -> &str {
+        "QR"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        match QrFactorization::new(&matrix.to_dense()) {
+            Ok(factorization) => SolveReport::direct(factorization.solve(f)),
+            Err(error) => SolveReport::failed(matrix.ncols(), error),
+        }
+    }
+}
+
+pub fn qr_solve(matrix: &DMatrix<f64>, b: &DVector<f64>) -> Result<DVector<f64>, FactorizationError> {
+    Ok(QrFactorization::new(matrix)?.solve(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::create_random_spd;
+
+    #[test]
+    fn factors_square_matrix() {
+        let matrix = create_random_spd(6, 10.0, 11).unwrap();
+        let factorization = QrFactorization::new(&matrix).unwrap();
+        let q = factorization.q();
+
+        assert!((&q * factorization.r() - &matrix).amax() < 1e-12);
+        assert!((q.transpose() * &q - DMatrix::identity(6, 6)).amax() < 1e-12);
+    }
+
+    // для переопределённой системы решение - МНК: прямая через точки (0, 1), (1, 3), (2, 5), (3, 7.5)
+    #[test]
+    fn solves_least_squares_problem() {
+        let matrix = DMatrix::from_row_slice(4, 2, &[1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let b = DVector::from_vec(vec![1.0, 3.0, 5.0, 7.5]);
+        let solution = qr_solve(&matrix, &b).unwrap();
+
+        let normal = (matrix.transpose() * &matrix).lu().solve(&(matrix.transpose() * &b)).unwrap();
+        assert!((solution - normal).norm() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_rank_deficient_and_underdetermined_matrices() {
+        let rank_deficient = DMatrix::from_row_slice(3, 3, &[1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0]);
+        assert!(matches!(QrFactorization::new(&rank_deficient), Err(FactorizationError::RankDeficient { .. })));
+        assert!(matches!(QrFactorization::new(&DMatrix::zeros(2, 3)), Err(FactorizationError::Underdetermined { rows: 2, cols: 3 })));
+    }
+
+    #[test]
+    fn zero_diagonal_zero_rhs_and_empty_system() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let solution = qr_solve(&matrix, &DVector::from_vec(vec![1.0, 2.0])).unwrap();
+        assert!((solution - DVector::from_vec(vec![2.0, 1.0])).norm() < 1e-12);
+
+        assert_eq!(qr_solve(&matrix, &DVector::zeros(2)).unwrap(), DVector::zeros(2));
+        assert!(qr_solve(&DMatrix::zeros(0, 0), &DVector::zeros(0)).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::gauss_pivot::gauss_pivot_solve;
+use super::lu::{LuFactorization, Pivoting};
+use super::matrix::SystemMatrix;
+use super::solver::{check_square, LinearSolver, SolveReport, SolverError, StopReason};
+use super::stopping::announce;
+
+// a + b = s + e точно (Knuth)
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let z = s - a;
+    (s, (a - (s - z)) + (b - z))
+}
+
+// a * b = p + e точно, ошибка округления произведения достаётся через fma
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+// r = b - A x со скомпенсированным суммированием (Dot2, Ogita - Rump - Oishi):
+// результат как при вычислении в удвоенной точности с последующим округлением до f64
+pub fn compensated_residual(matrix: &DMatrix<f64>, x: &DVector<f64>, b: &DVector<f64>) -> DVector<f64> {
+    DVector::from_fn(matrix.nrows(), |i, _| {
+        let mut sum = b[i];
+        let mut compensation = 0.0;
+        for j in 0..matrix.ncols() {
+            let (product, product_error) = two_product(-matrix[(i, j)], x[j]);
+            let (new_sum, sum_error) = two_sum(sum, product);
+            sum = new_sum;
+            compensation += product_error + sum_error;
+        }
+        sum + compensation
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    Double,
+    // разложение в f32, невязки и поправки в f64; сходится при cond(A) много меньше 1 / eps_f32 ≈ 1e7
+    Mixed,
+}
+
+// LU с выбором главного элемента по столбцу в одинарной точности: P A = L U
+#[derive(Debug, Clone)]
+pub struct SinglePrecisionLu {
+    lu: DMatrix<f32>,
+    permutation: Vec<usize>,
+}
+
+impl SinglePrecisionLu {
+    pub fn new(matrix: &DMatrix<f64>) -> Result<Self, SolverError> {
+        check_square(matrix)?;
+        let n = matrix.nrows();
+        let mut lu = matrix.map(|value| value as f32);
+        let mut permutation: Vec<usize> = (0..n).collect();
+
+        for i in 0..n {
+            let pivot_row = (i..n).max_by(|&x, &y| lu[(x, i)].abs().total_cmp(&lu[(y, i)].abs())).unwrap();
+            if pivot_row != i {
+                lu.swap_rows(i, pivot_row);
+                permutation.swap(i, pivot_row);
+            }
+
+            // в том числе элементы, не представимые в f32
+            if lu[(i, i)] == 0.0 || !lu[(i, i)].is_finite() {
+                return Err(SolverError::ZeroPivot { row: i });
+            }
+
+            for j in (i + 1)..n {
+                let factor = lu[(j, i)] / lu[(i, i)];
+                lu[(j, i)] = factor;
+
+                for k in (i + 1)..n {
+                    lu[(j, k)] -= factor * lu[(i, k)];
+                }
+            }
+        }
+
+        Ok(SinglePrecisionLu { lu, permutation })
+    }
+
+    // правая часть округляется до f32, ответ возвращается в f64
+    pub fn solve(&self, b: &DVector<f64>) -> DVector<f64> {
+        let n = self.lu.nrows();
+
+        let mut y = DVector::from_fn(n, |i, _| b[self.permutation[i]] as f32);
+        for i in 0..n {
+            let sum: f32 = (0..i).map(|k| self.lu[(i, k)] * y[k]).sum();
+            y[i] -= sum;
+        }
+
+        for i in (0..n).rev() {
+            let sum: f32 = (i + 1..n).map(|j| self.lu[(i, j)] * y[j]).sum();
+            y[i] = (y[i] - sum) / self.lu[(i, i)];
+        }
+
+        y.map(|value| value as f64)
+    }
+}
+
+// x_{k+1} = x_k + solve(r_k), r_k = b - A x_k считается скомпенсированно;
+// невязка мала уже после первого решения, поэтому уточнение продолжается, пока убывают поправки -
+// от них зависит прямая ошибка; в residuals - ||r_k||, начиная с невязки первого решения,
+// iterations - число шагов уточнения
+pub fn refine(matrix: &DMatrix<f64>, b: &DVector<f64>, solve: impl Fn(&DVector<f64>) -> Result<DVector<f64>, SolverError>,
+    tolerance: f64, max_steps: usize) -> Result<SolveReport, SolverError> {
+    check_square(matrix)?;
+    if b.len() != matrix.nrows() {
+        return Err(SolverError::InvalidParameter { name: "right-hand side length", value: b.len() as f64 });
+    }
+
+    let mut x = solve(b)?;
+    let mut r = compensated_residual(matrix, &x, b);
+    let mut residuals = vec![r.norm()];
+    let mut previous_correction = f64::INFINITY;
+
+    for step in 0..max_steps {
+        let correction = solve(&r)?;
+        let correction_norm = correction.norm();
+        let candidate = &x + &correction;
+        let candidate_residual = compensated_residual(matrix, &candidate, b);
+
+        // поправки перестали убывать хотя бы вдвое или невязка выросла (в том числе NaN):
+        // точность исчерпана либо разложение слишком грубое, x_k лучше
+        let improved = correction_norm < 0.5 * previous_correction && candidate_residual.norm() <= *residuals.last().unwrap();
+        if !improved {
+            return Ok(refinement_report(x, residuals, step, tolerance, StopReason::Stagnated));
+        }
+
+        x = candidate;
+        r = candidate_residual;
+        residuals.push(r.norm());
+        previous_correction = correction_norm;
+
+        // поправка на уровне округлений - x уже верно с рабочей точностью
+        if correction_norm <= f64::EPSILON * x.norm() {
+            return Ok(refinement_report(x, residuals, step + 1, tolerance, StopReason::Stagnated));
+        }
+    }
+
+    Ok(refinement_report(x, residuals, max_steps, tolerance, StopReason::MaxIterations))
+}
+
+// успех определяется итоговой невязкой, иначе - причина остановки
+fn refinement_report(x: DVector<f64>, residuals: Vec<f64>, steps: usize, tolerance: f64, otherwise: StopReason) -> SolveReport {
+    let stop_reason = if *residuals.last().unwrap() <= tolerance { StopReason::Converged } else { otherwise };
+    SolveReport::iterative(x, residuals, steps, stop_reason)
+}
+
+pub struct IterativeRefinement {
+    pub precision: Precision,
+    pub tolerance: f64,
+    pub max_steps: usize,
+    // как StoppingCriterion::quiet: без строки "converged in ..."
+    pub quiet: bool,
+}
+
+impl IterativeRefinement {
+    pub fn new(precision: Precision, tolerance: f64, max_steps: usize) -> Self {
+        IterativeRefinement { precision, tolerance, max_steps, quiet: false }
+    }
+
+    pub fn quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+}
+
+impl LinearSolver for IterativeRefinement {
+    fn name(&self) -> &str {
+        match self.precision {
+            Precision::Double => "LU with iterative refinement",
+            Precision::Mixed => "Mixed-precision LU",
+        }
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        let dense = matrix.to_dense();
+        let report = match self.precision {
+            Precision::Double => refined_lu_solve(&dense, f, self.tolerance, self.max_steps),
+            Precision::Mixed => mixed_precision_lu_solve(&dense, f, self.tolerance, self.max_steps),
+        };
+        let report = match report {
+            Ok(report) => report,
+            Err(error) => return SolveReport::failed(f.len(), error),
+        };
+
+        if !self.quiet {
+            announce(self.name(), report.iterations, report.stop_reason);
+        }
+        report
+    }
+}
+
+pub fn refined_lu_solve(matrix: &DMatrix<f64>, b: &DVector<f64>, tolerance: f64, max_steps: usize) -> Result<SolveReport, SolverError> {
+    let factorization = LuFactorization::new(matrix, Pivoting::Partial)?;
+    refine(matrix, b, |r| factorization.solve(r), tolerance, max_steps)
+}
+
+// разложение не сохраняется, поэтому исключение повторяется на каждом шаге: O(n^3) за шаг
+pub fn refined_gauss_pivot_solve(matrix: &DMatrix<f64>, b: &DVector<f64>, tolerance: f64, max_steps: usize) -> Result<SolveReport, SolverError> {
+    refine(matrix, b, |r| gauss_pivot_solve(matrix, r), tolerance, max_steps)
+}
+
+pub fn mixed_precision_lu_solve(matrix: &DMatrix<f64>, b: &DVector<f64>, tolerance: f64, max_steps: usize)
+    -> Result<SolveReport, SolverError> {
+    let factorization = SinglePrecisionLu::new(matrix)?;
+    refine(matrix, b, |r| Ok(factorization.solve(r)), tolerance, max_steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_custom_matrix, create_hilbert_matrix, create_random_spd, create_vector_f, ExactSolution};
+    use crate::methods::solver::FactorizationError;
+
+    #[test]
+    fn compensated_residual_is_exact_for_cancelling_terms() {
+        // 1e16 + 1 - 1e16: обычное суммирование теряет единицу
+        let matrix = DMatrix::from_row_slice(1, 3, &[-1e16, -1.0, 1e16]);
+        let x = DVector::from_element(3, 1.0);
+        assert_eq!(compensated_residual(&matrix, &x, &DVector::zeros(1))[0], 1.0);
+    }
+
+    #[test]
+    fn refinement_does_not_increase_residual() {
+        let matrix = create_hilbert_matrix(10);
+        let f = &matrix * ExactSolution::Ones.vector(10);
+        let report = refined_lu_solve(&matrix, &f, 1e-14, 10).unwrap();
+
+        assert!(report.residuals.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(*report.residuals.last().unwrap() <= report.residuals[0]);
+    }
+
+    #[test]
+    fn mixed_precision_reaches_double_accuracy_on_well_conditioned_system() {
+        let matrix = create_random_spd(50, 10.0, 7).unwrap();
+        let x = ExactSolution::Smooth.vector(50);
+        let report = mixed_precision_lu_solve(&matrix, &(&matrix * &x), 1e-12, 20).unwrap();
+
+        assert!(report.converged);
+        assert!((report.solution - x).norm() < 1e-12);
+    }
+
+    #[test]
+    fn gauss_refinement_solves_banded_system() {
+        let matrix = create_custom_matrix(30, 10.0);
+        let f = create_vector_f(30);
+        let report = refined_gauss_pivot_solve(&matrix, &f, 1e-10, 5).unwrap();
+
+        assert!(report.converged);
+        assert!((&matrix * report.solution - f).norm() < 1e-10);
+    }
+
+    #[test]
+    fn quiet_refinement_still_reports_convergence() {
+        let matrix = create_custom_matrix(10, 10.0);
+        let f = create_vector_f(10);
+        let solver = IterativeRefinement::new(Precision::Double, 1e-10, 5).quiet();
+        assert!(solver.quiet);
+        assert!(solver.solve(&matrix, &f).converged);
+    }
+
+    #[test]
+    fn non_square_matrix_and_wrong_rhs_length_are_errors() {
+        let matrix = DMatrix::from_element(2, 3, 1.0);
+        assert!(matches!(SinglePrecisionLu::new(&matrix), Err(SolverError::Factorization(FactorizationError::NotSquare { rows: 2, cols: 3 }))));
+        assert!(matches!(mixed_precision_lu_solve(&matrix, &DVector::zeros(2), 1e-10, 5), Err(SolverError::Factorization(_))));
+
+        let identity = DMatrix::identity(3, 3);
+        let error = refined_gauss_pivot_solve(&identity, &DVector::zeros(2), 1e-10, 5);
+        assert!(matches!(error, Err(SolverError::InvalidParameter { name: "right-hand side length", .. })));
+    }
+
+    #[test]
+    fn singular_matrix_is_an_error() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        assert!(matches!(SinglePrecisionLu::new(&matrix), Err(SolverError::ZeroPivot { row: 1 })));
+        assert!(matches!(refined_lu_solve(&matrix, &DVector::zeros(2), 1e-10, 5), Err(SolverError::ZeroPivot { .. })));
+
+        let report = IterativeRefinement::new(Precision::Mixed, 1e-10, 5).solve(&matrix, &DVector::zeros(2));
+        assert!(!report.converged);
+    }
+
+    #[test]
+    fn zero_diagonal_zero_rhs_and_empty_system() {
+        let swap = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let report = mixed_precision_lu_solve(&swap, &DVector::from_vec(vec![2.0, 3.0]), 1e-12, 5).unwrap();
+        assert_eq!(report.solution, DVector::from_vec(vec![3.0, 2.0]));
+
+        let report = refined_lu_solve(&create_custom_matrix(5, 10.0), &DVector::zeros(5), 1e-12, 5).unwrap();
+        assert!(report.converged);
+        assert_eq!(report.solution, DVector::zeros(5));
+
+        assert!(refined_lu_solve(&DMatrix::zeros(0, 0), &DVector::zeros(0), 1e-12, 5).unwrap().converged);
+    }
+}
@@ -1,39 +1,166 @@
-use nalgebra::{DMatrix, DVector};
+use nalgebra::DVector;
 
-pub fn seidel_solve(matrix: &DMatrix<f64>, b: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
-    let n = matrix.nrows();
-    let mut x = DVector::zeros(n);
-    let mut residuals = Vec::new(); 
+use super::diagnostics::relaxation_precheck;
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::ordering::{color_classes, relaxation_sweep, Ordering, Sweep};
+use super::parallel::{mul_vec, Execution};
+use super::solver::{LinearSolver, SolveReport, StopReason};
+use super::stopping::StoppingCriterion;
 
-    for iteration in 0..max_iterations {
-        let mut x_new = x.clone(); 
+pub struct Seidel {
+    pub criterion: StoppingCriterion,
+    // дешёвая проверка до начала итераций: квадратная матрица без нулей на диагонали
+    pub precheck: bool,
+    pub ordering: Ordering,
+    pub sweep: Sweep,
+    // параллельно обновляются только неизвестные одного цвета, поэтому имеет смысл лишь вместе с раскраской
+    pub execution: Execution,
+}
+
+impl Seidel {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Seidel { criterion: StoppingCriterion::step_norm(tolerance, max_iterations), precheck: true, ordering: Ordering::Natural, sweep: Sweep::Forward, execution: Execution::Serial }
+    }
 
-        for i in 0..n {
-            let mut sum1 = 0.0;
-            let mut sum2 = 0.0;
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    pub fn without_precheck(mut self) -> Self {
+        self.precheck = false;
+        self
+    }
 
-            for j in 0..i {
-                sum1 += matrix[(i, j)] * x_new[j];
+    pub fn with_ordering(mut self, ordering: Ordering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    pub fn with_sweep(mut self, sweep: Sweep) -> Self {
+        self.sweep = sweep;
+        self
+    }
+
+    pub fn with_execution(mut self, execution: Execution) -> Self {
+        self.execution = execution;
+        self
+    }
+}
+
+impl LinearSolver for Seidel {
+    fn name(&self) -> &str {
+        "Seidel"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, b, &mut ())
+    }
+
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let n = matrix.nrows();
+        let colors = match color_classes(matrix, self.ordering) {
+            Ok(colors) => colors,
+            Err(error) => {
+                let report = SolveReport::failed(n, error);
+                observer.on_finish(&report);
+                return report;
+            }
+        };
+        if self.precheck {
+            if let Err(error) = relaxation_precheck(matrix, 1.0) {
+                let report = SolveReport::precheck_failed(n, error);
+                observer.on_finish(&report);
+                return report;
             }
+        }
+
+        let mut x = DVector::zeros(n);
+
+        let mut monitor = self.criterion.monitor(b).with_observer(observer);
 
-            for j in i + 1..n {
-                sum2 += matrix[(i, j)] * x[j];
+        for iteration in 0..monitor.max_iterations() {
+            let mut x_new = x.clone(); 
+
+            relaxation_sweep(matrix, b, &mut x_new, 1.0, colors.as_deref(), self.sweep, self.execution);
+
+            let residual = b - mul_vec(self.execution, matrix, &x_new);
+
+            if let Some(stop_reason) = monitor.check(residual.norm(), Some((&x_new - &x).norm())) {
+                monitor.announce("The Seidel method", iteration + 1, stop_reason);
+                return monitor.finish(x_new, iteration + 1, stop_reason);
             }
 
-            x_new[i] = (b[i] - sum1 - sum2) / matrix[(i, i)];
+            x = x_new;
         }
 
-        let residual = b - matrix * &x_new;
-        residuals.push(residual.norm());
+        let max_iterations = monitor.max_iterations();
+        monitor.announce("The Seidel method", max_iterations, StopReason::MaxIterations);
+        monitor.finish(x, max_iterations, StopReason::MaxIterations)
+    }
+}
+
+pub fn seidel_solve(matrix: &dyn SystemMatrix, b: &DVector<f64>, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = Seidel::new(tolerance, max_iterations).solve(matrix, b);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+
+    use crate::methods::generators::{create_custom_sparse_matrix, create_poisson_2d, create_vector_f};
+    use crate::methods::solver::SolverError;
+
+    #[test]
+    fn solves_banded_system() {
+        let matrix = create_custom_sparse_matrix(50, 10.0);
+        let f = create_vector_f(50);
+        let report = Seidel::new(1e-12, 1000).solve(&matrix, &f);
 
-        if (&x_new - &x).norm() < tolerance {
-            println!("The Seidel method converged in {} iterations", iteration + 1);
-            return (x_new, residuals);
+        assert!(report.converged);
+        assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-10);
+    }
+
+    #[test]
+    fn orderings_and_sweeps_reach_the_same_solution() {
+        let matrix = create_poisson_2d(7);
+        let f = create_vector_f(49);
+        let criterion = StoppingCriterion::relative_residual(1e-10, 10000);
+
+        for ordering in [Ordering::Natural, Ordering::RedBlack, Ordering::Multicolor] {
+            for sweep in [Sweep::Forward, Sweep::Symmetric] {
+                let report = Seidel::new(1e-10, 10000).with_criterion(criterion.clone()).with_ordering(ordering).with_sweep(sweep).solve(&matrix, &f);
+                assert!(report.converged, "{:?} {:?}", ordering, sweep);
+                assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-9 * f.norm());
+            }
         }
+    }
 
-        x = x_new;
+    #[test]
+    fn zero_diagonal_fails_precheck() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let report = Seidel::new(1e-10, 100).solve(&matrix, &DVector::from_element(2, 1.0));
+
+        assert_eq!(report.stop_reason, StopReason::PrecheckFailed);
+        assert!(matches!(report.error, Some(SolverError::Precheck(_))));
+    }
+
+    #[test]
+    fn zero_rhs_converges_at_first_step() {
+        let report = Seidel::new(1e-10, 100).solve(&create_custom_sparse_matrix(10, 10.0), &DVector::zeros(10));
+        assert!(report.converged);
+        assert_eq!(report.solution, DVector::zeros(10));
     }
 
-    println!("Warning!!! The Seidel method did not converge for {} iterations.", max_iterations);
-    (x, residuals) 
+    #[test]
+    fn empty_system_converges() {
+        for ordering in [Ordering::Natural, Ordering::RedBlack, Ordering::Multicolor] {
+            let report = Seidel::new(1e-10, 100).with_ordering(ordering).solve(&DMatrix::zeros(0, 0), &DVector::zeros(0));
+            assert!(report.converged, "{:?}", ordering);
+            assert!(report.solution.is_empty());
+        }
+    }
 }
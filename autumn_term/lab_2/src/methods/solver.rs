@@ -0,0 +1,282 @@
+use nalgebra::{DMatrix, DVector};
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::time::{Duration, Instant};
+
+use super::diagnostics::ConvergenceError;
+use super::matrix::SystemMatrix;
+use super::observer::{IterationEvent, IterationObserver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Direct,
+    Converged,
+    MaxIterations,
+    Breakdown,
+    Diverged,
+    Stagnated,
+    TimeLimit,
+    // остановлено наблюдателем (IterationObserver)
+    Interrupted,
+    // предварительная проверка показала, что метод не сойдётся
+    PrecheckFailed,
+}
+
+// при отказе метода solution - последнее приближение (или NaN у прямых методов), причина - в error
+#[derive(Debug)]
+pub struct SolveReport {
+    pub solution: DVector<f64>,
+    pub residuals: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+    pub stop_reason: StopReason,
+    pub error: Option<SolverError>,
+    // параметр релаксации, с которым решал метод верхней релаксации; у остальных методов None
+    pub omega: Option<f64>,
+}
+
+impl SolveReport {
+    pub fn direct(solution: DVector<f64>) -> Self {
+        SolveReport {
+            solution,
+            residuals: Vec::new(),
+            iterations: 0,
+            converged: true,
+            stop_reason: StopReason::Direct,
+            error: None,
+            omega: None,
+        }
+    }
+
+    // если метод не сошёлся, в error записывается NotConverged с последней невязкой
+    pub fn iterative(solution: DVector<f64>, residuals: Vec<f64>, iterations: usize, stop_reason: StopReason) -> Self {
+        let error = (stop_reason != StopReason::Converged).then(|| SolverError::NotConverged {
+            iterations,
+            residual: residuals.last().copied().unwrap_or(f64::NAN),
+            stop_reason,
+        });
+
+        SolveReport {
+            solution,
+            residuals,
+            iterations,
+            converged: stop_reason == StopReason::Converged,
+            stop_reason,
+            error,
+            omega: None,
+        }
+    }
+
+    pub fn precheck_failed(n: usize, error: ConvergenceError) -> Self {
+        SolveReport {
+            solution: DVector::zeros(n),
+            residuals: Vec::new(),
+            iterations: 0,
+            converged: false,
+            stop_reason: StopReason::PrecheckFailed,
+            error: Some(SolverError::Precheck(error)),
+            omega: None,
+        }
+    }
+
+    // прямой метод не смог выполнить разложение
+    pub fn failed(n: usize, error: impl Into<SolverError>) -> Self {
+        SolveReport {
+            solution: DVector::from_element(n, f64::NAN),
+            residuals: Vec::new(),
+            iterations: 0,
+            converged: false,
+            stop_reason: StopReason::Breakdown,
+            error: Some(error.into()),
+            omega: None,
+        }
+    }
+
+    // уточнение причины, например, конкретные значения при срыве итераций
+    pub fn with_error(mut self, error: SolverError) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    pub fn with_omega(mut self, omega: f64) -> Self {
+        self.omega = Some(omega);
+        self
+    }
+
+    pub fn into_result(mut self) -> Result<SolveReport, SolverError> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(self),
+        }
+    }
+}
+
+pub trait LinearSolver {
+    fn name(&self) -> &str;
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport;
+
+    // итерационные методы вызывают наблюдателя на каждой итерации;
+    // остальные передают ему историю невязок уже после решения, без нормы шага
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let start = Instant::now();
+        let report = self.solve(matrix, f);
+        replay(observer, &report, start.elapsed());
+        report
+    }
+
+    // то же решение, но отказ метода возвращается как ошибка
+    fn try_solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> Result<SolveReport, SolverError> {
+        self.solve(matrix, f).into_result()
+    }
+}
+
+// история готового решения целиком; остановка по просьбе наблюдателя здесь уже невозможна
+pub(crate) fn replay(observer: &mut dyn IterationObserver, report: &SolveReport, elapsed: Duration) {
+    for (iteration, &residual) in report.residuals.iter().enumerate() {
+        observer.on_iteration(&IterationEvent { iteration, residual, step_norm: None, elapsed });
+    }
+    observer.on_finish(report);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FactorizationError {
+    NotSquare { rows: usize, cols: usize },
+    Underdetermined { rows: usize, cols: usize },
+    NotSymmetric { row: usize, col: usize },
+    NotPositiveDefinite { row: usize, pivot: f64 },
+    ZeroPivot { row: usize },
+    RankDeficient { column: usize },
+}
+
+impl fmt::Display for FactorizationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FactorizationError::NotSquare { rows, cols } => write!(f, "matrix is not square ({}x{})", rows, cols),
+            FactorizationError::Underdetermined { rows, cols } => write!(f, "system is underdetermined ({}x{})", rows, cols),
+            FactorizationError::NotSymmetric { row, col } => write!(f, "matrix is not symmetric: a[{}][{}] != a[{}][{}]", row, col, col, row),
+            FactorizationError::NotPositiveDefinite { row, pivot } => write!(f, "matrix is not positive definite: pivot {:e} at row {}", pivot, row),
+            FactorizationError::ZeroPivot { row } => write!(f, "zero pivot at row {}", row),
+            FactorizationError::RankDeficient { column } => write!(f, "matrix is rank deficient at column {}", column),
+        }
+    }
+}
+
+impl Error for FactorizationError {}
+
+pub fn check_square(matrix: &DMatrix<f64>) -> Result<(), FactorizationError> {
+    if !matrix.is_square() {
+        return Err(FactorizationError::NotSquare { rows: matrix.nrows(), cols: matrix.ncols() });
+    }
+
+    Ok(())
+}
+
+pub fn check_symmetric(matrix: &DMatrix<f64>) -> Result<(), FactorizationError> {
+    check_square(matrix)?;
+
+    let tolerance = 1e-12 * matrix.amax();
+    for i in 0..matrix.nrows() {
+        for j in 0..i {
+            if (matrix[(i, j)] - matrix[(j, i)]).abs() > tolerance {
+                return Err(FactorizationError::NotSymmetric { row: i, col: j });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// общая ошибка решателей: всё, из-за чего решение не получено или не достигло заданной точности
+#[derive(Debug)]
+pub enum SolverError {
+    Singular,
+    ZeroPivot { row: usize },
+    // прочие причины, по которым разложение невозможно
+    Factorization(FactorizationError),
+    // метод не запускался: предварительная проверка показала, что он не сойдётся
+    Precheck(ConvergenceError),
+    // срыв BiCGSTAB: rho = (r^, r) или omega обратились в ноль
+    Breakdown { iteration: usize, rho: f64, omega: f64 },
+    // CG на матрице, не являющейся SPD: p^T A p <= 0
+    Indefinite { iteration: usize, curvature: f64 },
+    // параметр метода вне допустимой области, например omega SSOR вне (0, 2)
+    InvalidParameter { name: &'static str, value: f64 },
+    // итерациям Чебышёва нужны границы спектра 0 < lambda_min < lambda_max
+    SpectrumBounds { lambda_min: f64, lambda_max: f64 },
+    NotTridiagonal { lower: usize, upper: usize },
+    // красно-чёрный порядок неприменим: граф матрицы не двудольный
+    NotBipartite,
+    NotConverged { iterations: usize, residual: f64, stop_reason: StopReason },
+    Io(io::Error),
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolverError::Singular => write!(f, "matrix is singular"),
+            SolverError::ZeroPivot { row } => write!(f, "zero pivot at row {}", row),
+            SolverError::Factorization(error) => write!(f, "{}", error),
+            SolverError::Precheck(error) => write!(f, "not started: {}", error),
+            SolverError::Breakdown { iteration, rho, omega } => {
+                write!(f, "broke down at iteration {} (rho = {:e}, omega = {:e})", iteration, rho, omega)
+            }
+            SolverError::Indefinite { iteration, curvature } => {
+                write!(f, "broke down at iteration {} (p^T A p = {:e}), matrix is not SPD", iteration, curvature)
+            }
+            SolverError::InvalidParameter { name, value } => write!(f, "invalid parameter {} = {}", name, value),
+            SolverError::SpectrumBounds { lambda_min, lambda_max } => {
+                write!(f, "spectrum bounds must satisfy 0 < lambda_min < lambda_max, got [{:e}, {:e}]", lambda_min, lambda_max)
+            }
+            SolverError::NotTridiagonal { lower, upper } => write!(f, "needs a tridiagonal matrix, got bandwidth ({}, {})", lower, upper),
+            SolverError::NotBipartite => write!(f, "red-black ordering needs a bipartite matrix graph"),
+            SolverError::NotConverged { iterations, residual, stop_reason } => {
+                let reason = match stop_reason {
+                    StopReason::Diverged => "diverged",
+                    StopReason::Stagnated => "stagnated",
+                    StopReason::TimeLimit => "exceeded its time budget",
+                    StopReason::Interrupted => "was stopped by an observer",
+                    StopReason::Breakdown => "broke down",
+                    _ => "did not converge",
+                };
+                write!(f, "{} after {} iterations (last residual {:e})", reason, iterations, residual)
+            }
+            SolverError::Io(error) => write!(f, "I/O error: {}", error),
+        }
+    }
+}
+
+impl Error for SolverError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolverError::Factorization(error) => Some(error),
+            SolverError::Precheck(error) => Some(error),
+            SolverError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// нулевой ведущий элемент и неполный ранг получают собственные варианты
+impl From<FactorizationError> for SolverError {
+    fn from(error: FactorizationError) -> Self {
+        match error {
+            FactorizationError::ZeroPivot { row } => SolverError::ZeroPivot { row },
+            FactorizationError::RankDeficient { .. } => SolverError::Singular,
+            error => SolverError::Factorization(error),
+        }
+    }
+}
+
+impl From<ConvergenceError> for SolverError {
+    fn from(error: ConvergenceError) -> Self {
+        SolverError::Precheck(error)
+    }
+}
+
+impl From<io::Error> for SolverError {
+    fn from(error: io::Error) -> Self {
+        SolverError::Io(error)
+    }
+}
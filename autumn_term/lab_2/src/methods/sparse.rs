@@ -0,0 +1,298 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::matrix::SystemMatrix;
+
+#[derive(Debug, Clone)]
+pub struct CsrMatrix {
+    nrows: usize,
+    ncols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl CsrMatrix {
+    // повторяющиеся позиции суммируются, как в scipy.sparse
+    pub fn from_triplets(nrows: usize, ncols: usize, triplets: &[(usize, usize, f64)]) -> Self {
+        let mut sorted = triplets.to_vec();
+        sorted.sort_by_key(|&(i, j, _)| (i, j));
+
+        let mut row_offsets = vec![0; nrows + 1];
+        let mut col_indices: Vec<usize> = Vec::with_capacity(sorted.len());
+        let mut values: Vec<f64> = Vec::with_capacity(sorted.len());
+        let mut last: Option<(usize, usize)> = None;
+
+        for &(i, j, value) in &sorted {
+            assert!(i < nrows && j < ncols, "Triplet ({}, {}) is out of bounds for {}x{} matrix", i, j, nrows, ncols);
+
+            if last == Some((i, j)) {
+                *values.last_mut().unwrap() += value;
+                continue;
+            }
+
+            col_indices.push(j);
+            values.push(value);
+            row_offsets[i + 1] += 1;
+            last = Some((i, j));
+        }
+
+        for i in 0..nrows {
+            row_offsets[i + 1] += row_offsets[i];
+        }
+
+        CsrMatrix { nrows, ncols, row_offsets, col_indices, values }
+    }
+
+    // bands: пары (смещение диагонали, значение); 0 - главная, >0 - над ней, <0 - под ней
+    pub fn from_bands(n: usize, bands: &[(isize, f64)]) -> Self {
+        let mut sorted = bands.to_vec();
+        sorted.sort_by_key(|&(offset, _)| offset);
+
+        let mut row_offsets = Vec::with_capacity(n + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_offsets.push(0);
+
+        for i in 0..n {
+            for &(offset, value) in &sorted {
+                let j = i as isize + offset;
+                if j >= 0 && (j as usize) < n {
+                    col_indices.push(j as usize);
+                    values.push(value);
+                }
+            }
+            row_offsets.push(col_indices.len());
+        }
+
+        CsrMatrix { nrows: n, ncols: n, row_offsets, col_indices, values }
+    }
+
+    pub fn from_dense(matrix: &DMatrix<f64>) -> Self {
+        let mut triplets = Vec::new();
+        for i in 0..matrix.nrows() {
+            for j in 0..matrix.ncols() {
+                if matrix[(i, j)] != 0.0 {
+                    triplets.push((i, j, matrix[(i, j)]));
+                }
+            }
+        }
+
+        CsrMatrix::from_triplets(matrix.nrows(), matrix.ncols(), &triplets)
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        self.col_indices[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        match self.col_indices[range.clone()].binary_search(&j) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => 0.0,
+        }
+    }
+
+    pub fn triplets(&self) -> Vec<(usize, usize, f64)> {
+        (0..self.nrows).flat_map(|i| self.row(i).map(move |(j, value)| (i, j, value))).collect()
+    }
+
+    pub fn transpose(&self) -> CsrMatrix {
+        let mut row_offsets = vec![0; self.ncols + 1];
+        for &j in &self.col_indices {
+            row_offsets[j + 1] += 1;
+        }
+        for j in 0..self.ncols {
+            row_offsets[j + 1] += row_offsets[j];
+        }
+
+        let mut next = row_offsets.clone();
+        let mut col_indices = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        for i in 0..self.nrows {
+            for (j, value) in self.row(i) {
+                col_indices[next[j]] = i;
+                values[next[j]] = value;
+                next[j] += 1;
+            }
+        }
+
+        CsrMatrix { nrows: self.ncols, ncols: self.nrows, row_offsets, col_indices, values }
+    }
+}
+
+impl SystemMatrix for CsrMatrix {
+    fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    fn mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        DVector::from_iterator(self.nrows, (0..self.nrows).map(|i| self.row_dot(i, x)))
+    }
+
+    fn tr_mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        let mut y = DVector::zeros(self.ncols);
+        for i in 0..self.nrows {
+            for (j, value) in self.row(i) {
+                y[j] += value * x[i];
+            }
+        }
+        y
+    }
+
+    fn row_dot(&self, i: usize, x: &DVector<f64>) -> f64 {
+        self.row(i).map(|(j, value)| value * x[j]).sum()
+    }
+
+    fn row_entries(&self, i: usize) -> Vec<(usize, f64)> {
+        self.row(i).collect()
+    }
+
+    fn diagonal(&self) -> DVector<f64> {
+        DVector::from_iterator(self.nrows.min(self.ncols), (0..self.nrows.min(self.ncols)).map(|i| self.get(i, i)))
+    }
+
+    fn to_dense(&self) -> DMatrix<f64> {
+        let mut dense = DMatrix::zeros(self.nrows, self.ncols);
+        for i in 0..self.nrows {
+            for (j, value) in self.row(i) {
+                dense[(i, j)] = value;
+            }
+        }
+        dense
+    }
+}
+
+// CSC хранится как CSR транспонированной матрицы; рядом - строчный индекс (та же матрица в CSR),
+// он строится один раз и вдвое увеличивает память, зато строка доступна за O(nnz строки)
+#[derive(Debug, Clone)]
+pub struct CscMatrix {
+    transposed: CsrMatrix,
+    rows: CsrMatrix,
+}
+
+impl CscMatrix {
+    pub fn from_csr(matrix: &CsrMatrix) -> Self {
+        CscMatrix { transposed: matrix.transpose(), rows: matrix.clone() }
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix {
+        self.rows.clone()
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.transposed.nnz()
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.transposed.row(j)
+    }
+}
+
+// умножение - разброс по столбцам, строки для методов Зейделя и SOR берутся из строчного индекса
+impl SystemMatrix for CscMatrix {
+    fn nrows(&self) -> usize {
+        self.transposed.ncols()
+    }
+
+    fn ncols(&self) -> usize {
+        self.transposed.nrows()
+    }
+
+    fn mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        let mut y = DVector::zeros(self.nrows());
+        for j in 0..self.ncols() {
+            for (i, value) in self.column(j) {
+                y[i] += value * x[j];
+            }
+        }
+        y
+    }
+
+    fn tr_mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        self.transposed.mul_vec(x)
+    }
+
+    fn row_dot(&self, i: usize, x: &DVector<f64>) -> f64 {
+        self.rows.row_dot(i, x)
+    }
+
+    fn row_entries(&self, i: usize) -> Vec<(usize, f64)> {
+        self.rows.row_entries(i)
+    }
+
+    fn diagonal(&self) -> DVector<f64> {
+        self.transposed.diagonal()
+    }
+
+    fn to_dense(&self) -> DMatrix<f64> {
+        self.transposed.to_dense().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> DMatrix<f64> {
+        DMatrix::from_row_slice(3, 4, &[
+            4.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 2.0,
+            1.0, 3.0, 0.0, 5.0,
+        ])
+    }
+
+    #[test]
+    fn triplets_are_summed_and_sorted() {
+        let matrix = CsrMatrix::from_triplets(2, 2, &[(1, 0, 1.0), (0, 1, 2.0), (1, 0, 3.0)]);
+        assert_eq!(matrix.nnz(), 2);
+        assert_eq!(matrix.get(1, 0), 4.0);
+        assert_eq!(matrix.triplets(), vec![(0, 1, 2.0), (1, 0, 4.0)]);
+    }
+
+    #[test]
+    fn csr_matches_dense() {
+        let dense = example();
+        let matrix = CsrMatrix::from_dense(&dense);
+        let x = DVector::from_vec(vec![1.0, -2.0, 3.0, 0.5]);
+        let y = DVector::from_vec(vec![1.0, 2.0, -1.0]);
+
+        assert_eq!(matrix.to_dense(), dense);
+        assert_eq!(matrix.mul_vec(&x), &dense * &x);
+        assert_eq!(matrix.tr_mul_vec(&y), dense.tr_mul(&y));
+        assert_eq!(matrix.transpose().to_dense(), dense.transpose());
+        assert_eq!(matrix.diagonal(), DVector::from_vec(vec![4.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn csc_matches_dense() {
+        let dense = example();
+        let matrix = CscMatrix::from_csr(&CsrMatrix::from_dense(&dense));
+        let x = DVector::from_vec(vec![1.0, -2.0, 3.0, 0.5]);
+        let y = DVector::from_vec(vec![1.0, 2.0, -1.0]);
+
+        assert_eq!((matrix.nrows(), matrix.ncols()), (3, 4));
+        assert_eq!(matrix.to_dense(), dense);
+        assert_eq!(matrix.mul_vec(&x), &dense * &x);
+        assert_eq!(matrix.tr_mul_vec(&y), dense.tr_mul(&y));
+        assert_eq!(matrix.row_entries(2), vec![(0, 1.0), (1, 3.0), (3, 5.0)]);
+        assert_eq!(matrix.row_dot(2, &x), 1.0 - 6.0 + 2.5);
+        assert_eq!(matrix.diagonal(), DVector::from_vec(vec![4.0, 0.0, 0.0]));
+        assert_eq!(matrix.to_csr().to_dense(), dense);
+    }
+
+    #[test]
+    fn bands_are_clipped_at_the_edges() {
+        let matrix = CsrMatrix::from_bands(3, &[(-1, -1.0), (0, 2.0), (1, -1.0)]);
+        assert_eq!(matrix.nnz(), 7);
+        assert_eq!(matrix.row_entries(0), vec![(0, 2.0), (1, -1.0)]);
+    }
+}
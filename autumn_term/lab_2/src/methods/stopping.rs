@@ -0,0 +1,281 @@
+use nalgebra::DVector;
+
+use std::time::{Duration, Instant};
+
+use super::observer::{IterationEvent, IterationObserver, ObserverAction};
+use super::solver::{SolveReport, SolverError, StopReason};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvergenceTest {
+    // ||r|| < tol
+    AbsoluteResidual(f64),
+    // ||r|| <= tol * ||f||; нестрогое неравенство, чтобы при f = 0 нулевая невязка считалась сходимостью
+    RelativeResidual(f64),
+    // ||x_{k+1} - x_k|| < tol
+    StepNorm(f64),
+    Any(Vec<ConvergenceTest>),
+    All(Vec<ConvergenceTest>),
+}
+
+impl ConvergenceTest {
+    // норма шага известна не во всех методах; если её нет, StepNorm не выполняется
+    pub fn is_satisfied(&self, residual_norm: f64, f_norm: f64, step_norm: Option<f64>) -> bool {
+        match self {
+            ConvergenceTest::AbsoluteResidual(tolerance) => residual_norm < *tolerance,
+            ConvergenceTest::RelativeResidual(tolerance) => residual_norm <= tolerance * f_norm,
+            ConvergenceTest::StepNorm(tolerance) => step_norm.is_some_and(|step| step < *tolerance),
+            ConvergenceTest::Any(tests) => tests.iter().any(|test| test.is_satisfied(residual_norm, f_norm, step_norm)),
+            ConvergenceTest::All(tests) => tests.iter().all(|test| test.is_satisfied(residual_norm, f_norm, step_norm)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoppingCriterion {
+    pub convergence: ConvergenceTest,
+    pub max_iterations: usize,
+    // расходимость: ||r|| > factor * ||r_0||; нечисловая невязка (NaN, inf) считается расходимостью всегда
+    pub divergence_factor: Option<f64>,
+    // застой: за window записей невязка уменьшилась меньше, чем в (1 - min_decrease) раз
+    pub stagnation: Option<(usize, f64)>,
+    pub time_budget: Option<Duration>,
+    // без строки "converged in ..." - для бенчмарков и многократных запусков
+    pub quiet: bool,
+}
+
+impl StoppingCriterion {
+    pub fn new(convergence: ConvergenceTest, max_iterations: usize) -> Self {
+        StoppingCriterion { convergence, max_iterations, divergence_factor: None, stagnation: None, time_budget: None, quiet: false }
+    }
+
+    pub fn absolute_residual(tolerance: f64, max_iterations: usize) -> Self {
+        StoppingCriterion::new(ConvergenceTest::AbsoluteResidual(tolerance), max_iterations)
+    }
+
+    pub fn relative_residual(tolerance: f64, max_iterations: usize) -> Self {
+        StoppingCriterion::new(ConvergenceTest::RelativeResidual(tolerance), max_iterations)
+    }
+
+    pub fn step_norm(tolerance: f64, max_iterations: usize) -> Self {
+        StoppingCriterion::new(ConvergenceTest::StepNorm(tolerance), max_iterations)
+    }
+
+    pub fn with_divergence_factor(mut self, factor: f64) -> Self {
+        self.divergence_factor = Some(factor);
+        self
+    }
+
+    pub fn with_stagnation(mut self, window: usize, min_decrease: f64) -> Self {
+        self.stagnation = Some((window, min_decrease));
+        self
+    }
+
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    pub fn quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    pub fn monitor(&self, f: &DVector<f64>) -> StoppingMonitor<'_> {
+        StoppingMonitor { criterion: self, f_norm: f.norm(), start: Instant::now(), residuals: Vec::new(), observer: None }
+    }
+}
+
+// состояние критерия на время одного решения; заодно собирает историю невязок
+pub struct StoppingMonitor<'a> {
+    criterion: &'a StoppingCriterion,
+    f_norm: f64,
+    start: Instant,
+    residuals: Vec<f64>,
+    observer: Option<&'a mut dyn IterationObserver>,
+}
+
+impl<'a> StoppingMonitor<'a> {
+    // наблюдатель получает каждую записанную невязку и может остановить метод
+    pub fn with_observer(mut self, observer: &'a mut dyn IterationObserver) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn max_iterations(&self) -> usize {
+        self.criterion.max_iterations
+    }
+
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+
+    pub fn announce(&self, method: &str, iterations: usize, stop_reason: StopReason) {
+        if !self.criterion.quiet {
+            announce(method, iterations, stop_reason);
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    // записывает невязку и возвращает причину остановки, если пора остановиться;
+    // остановка по просьбе наблюдателя уступает остальным причинам
+    pub fn check(&mut self, residual_norm: f64, step_norm: Option<f64>) -> Option<StopReason> {
+        self.residuals.push(residual_norm);
+        let stop_reason = self.criterion_stop(residual_norm, step_norm);
+
+        let action = match self.observer.as_mut() {
+            Some(observer) => {
+                let event = IterationEvent { iteration: self.residuals.len() - 1, residual: residual_norm, step_norm, elapsed: self.start.elapsed() };
+                observer.on_iteration(&event)
+            }
+            None => ObserverAction::Continue,
+        };
+
+        match action {
+            ObserverAction::Stop => stop_reason.or(Some(StopReason::Interrupted)),
+            ObserverAction::Continue => stop_reason,
+        }
+    }
+
+    // начальная невязка записывается, только если критерий уже выполнен (f = 0 или точное x0):
+    // тогда итерации не нужны, а шаги методов Крылова с нулевой невязкой не определены;
+    // нулевая невязка - точное решение при любом критерии, даже без нормы шага для StepNorm
+    pub fn initially_converged(&mut self, residual_norm: f64) -> bool {
+        let converged = residual_norm == 0.0 || self.criterion.convergence.is_satisfied(residual_norm, self.f_norm, None);
+        if converged {
+            self.check(residual_norm, None);
+        }
+        converged
+    }
+
+    fn criterion_stop(&self, residual_norm: f64, step_norm: Option<f64>) -> Option<StopReason> {
+        let criterion = self.criterion;
+
+        if criterion.convergence.is_satisfied(residual_norm, self.f_norm, step_norm) {
+            return Some(StopReason::Converged);
+        }
+
+        if !residual_norm.is_finite() {
+            return Some(StopReason::Diverged);
+        }
+        if let Some(factor) = criterion.divergence_factor {
+            if residual_norm > factor * self.residuals[0] {
+                return Some(StopReason::Diverged);
+            }
+        }
+
+        if let Some((window, min_decrease)) = criterion.stagnation {
+            let len = self.residuals.len();
+            if len > window {
+                let reference = self.residuals[len - 1 - window];
+                let best = self.residuals[len - window..].iter().cloned().fold(f64::INFINITY, f64::min);
+                if best >= (1.0 - min_decrease) * reference {
+                    return Some(StopReason::Stagnated);
+                }
+            }
+        }
+
+        if criterion.time_budget.is_some_and(|budget| self.start.elapsed() > budget) {
+            return Some(StopReason::TimeLimit);
+        }
+
+        None
+    }
+
+    pub fn finish(self, solution: DVector<f64>, iterations: usize, stop_reason: StopReason) -> SolveReport {
+        let report = SolveReport::iterative(solution, self.residuals, iterations, stop_reason);
+        if let Some(observer) = self.observer {
+            observer.on_finish(&report);
+        }
+        report
+    }
+
+    // срыв итераций с конкретной причиной; наблюдатель видит уже уточнённую ошибку
+    pub fn fail(self, solution: DVector<f64>, iterations: usize, error: SolverError) -> SolveReport {
+        let report = SolveReport::iterative(solution, self.residuals, iterations, StopReason::Breakdown).with_error(error);
+        if let Some(observer) = self.observer {
+            observer.on_finish(&report);
+        }
+        report
+    }
+}
+
+// отказ метода не печатается, он возвращается в SolveReport::error;
+// снаружи печать идёт через StoppingMonitor::announce, чтобы учитывался quiet
+pub(crate) fn announce(method: &str, iterations: usize, stop_reason: StopReason) {
+    if stop_reason == StopReason::Converged {
+        println!("{} converged in {} iterations", method, iterations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::observer::EarlyStop;
+
+    #[test]
+    fn relative_residual_accepts_zero_rhs() {
+        let test = ConvergenceTest::RelativeResidual(1e-8);
+        assert!(test.is_satisfied(0.0, 0.0, None));
+        assert!(!test.is_satisfied(1e-12, 0.0, None));
+        assert!(test.is_satisfied(1e-9, 1.0, None));
+    }
+
+    #[test]
+    fn combined_tests() {
+        let any = ConvergenceTest::Any(vec![ConvergenceTest::AbsoluteResidual(1e-8), ConvergenceTest::StepNorm(1e-8)]);
+        let all = ConvergenceTest::All(vec![ConvergenceTest::AbsoluteResidual(1e-8), ConvergenceTest::StepNorm(1e-8)]);
+
+        assert!(any.is_satisfied(1.0, 1.0, Some(1e-9)));
+        assert!(!all.is_satisfied(1.0, 1.0, Some(1e-9)));
+        assert!(all.is_satisfied(1e-9, 1.0, Some(1e-9)));
+        // без нормы шага StepNorm не выполняется
+        assert!(!all.is_satisfied(1e-9, 1.0, None));
+    }
+
+    #[test]
+    fn monitor_detects_divergence_and_stagnation() {
+        let f = DVector::from_element(2, 1.0);
+
+        let criterion = StoppingCriterion::absolute_residual(1e-8, 100).with_divergence_factor(10.0);
+        let mut monitor = criterion.monitor(&f);
+        assert_eq!(monitor.check(1.0, None), None);
+        assert_eq!(monitor.check(20.0, None), Some(StopReason::Diverged));
+
+        let mut monitor = criterion.monitor(&f);
+        assert_eq!(monitor.check(f64::NAN, None), Some(StopReason::Diverged));
+
+        let criterion = StoppingCriterion::absolute_residual(1e-8, 100).with_stagnation(2, 0.1);
+        let mut monitor = criterion.monitor(&f);
+        assert_eq!(monitor.check(1.0, None), None);
+        assert_eq!(monitor.check(0.95, None), None);
+        assert_eq!(monitor.check(0.95, None), Some(StopReason::Stagnated));
+        assert_eq!(monitor.residuals(), &[1.0, 0.95, 0.95]);
+    }
+
+    #[test]
+    fn observer_stop_yields_to_convergence() {
+        let f = DVector::from_element(2, 1.0);
+        let criterion = StoppingCriterion::absolute_residual(1e-8, 100);
+        let mut stop = EarlyStop::new(|_| true);
+
+        let mut monitor = criterion.monitor(&f).with_observer(&mut stop);
+        assert_eq!(monitor.check(1.0, None), Some(StopReason::Interrupted));
+        assert_eq!(monitor.check(0.0, None), Some(StopReason::Converged));
+    }
+
+    #[test]
+    fn initial_residual_is_recorded_only_when_converged() {
+        let criterion = StoppingCriterion::relative_residual(1e-8, 100);
+        let mut monitor = criterion.monitor(&DVector::zeros(3));
+        assert!(monitor.initially_converged(0.0));
+        assert_eq!(monitor.residuals(), &[0.0]);
+
+        let mut monitor = criterion.monitor(&DVector::from_element(3, 1.0));
+        assert!(!monitor.initially_converged(1.0));
+        assert!(monitor.residuals().is_empty());
+    }
+}
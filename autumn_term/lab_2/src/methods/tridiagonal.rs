@@ -0,0 +1,260 @@
+use nalgebra::{DMatrix, DVector};
+
+use super::band::BandMatrix;
+use super::lu::{LuFactorization, Pivoting};
+use super::matrix::SystemMatrix;
+use super::solver::{LinearSolver, SolveReport, SolverError};
+
+// метод прогонки без выбора ведущего элемента; lower[i] = a_{i+1,i}, upper[i] = a_{i,i+1}
+pub fn thomas_solve(lower: &[f64], diagonal: &[f64], upper: &[f64], f: &DVector<f64>) -> Result<DVector<f64>, SolverError> {
+    let n = diagonal.len();
+    if lower.len() != n.saturating_sub(1) || upper.len() != n.saturating_sub(1) {
+        return Err(SolverError::InvalidParameter { name: "off-diagonal length", value: lower.len().max(upper.len()) as f64 });
+    }
+    if f.len() != n {
+        return Err(SolverError::InvalidParameter { name: "right-hand side length", value: f.len() as f64 });
+    }
+    if n == 0 {
+        return Ok(DVector::zeros(0));
+    }
+
+    // прямой ход: прогоночные коэффициенты
+    let mut alpha = vec![0.0; n];
+    let mut beta = vec![0.0; n];
+    for i in 0..n {
+        let sub = if i > 0 { lower[i - 1] } else { 0.0 };
+        let previous_alpha = if i > 0 { alpha[i - 1] } else { 0.0 };
+        let previous_beta = if i > 0 { beta[i - 1] } else { 0.0 };
+
+        let denominator = diagonal[i] + sub * previous_alpha;
+        if denominator == 0.0 {
+            return Err(SolverError::ZeroPivot { row: i });
+        }
+        if i + 1 < n {
+            alpha[i] = -upper[i] / denominator;
+        }
+        beta[i] = (f[i] - sub * previous_beta) / denominator;
+    }
+
+    // обратный ход
+    let mut x = DVector::zeros(n);
+    x[n - 1] = beta[n - 1];
+    for i in (0..n - 1).rev() {
+        x[i] = alpha[i] * x[i + 1] + beta[i];
+    }
+
+    Ok(x)
+}
+
+pub struct Thomas;
+
+impl LinearSolver for Thomas {
+    fn name(&self) -> &str {
+        "Thomas"
+    }
+
+    fn solve(&self, matrix: &dyn SystemMatrix, f: &DVector<f64>) -> SolveReport {
+        let band = match BandMatrix::from_system_with_rhs(matrix, f) {
+            Ok(band) => band,
+            Err(error) => return SolveReport::failed(f.len(), error),
+        };
+        if band.lower() > 1 || band.upper() > 1 {
+            return SolveReport::failed(f.len(), SolverError::NotTridiagonal { lower: band.lower(), upper: band.upper() });
+        }
+
+        let n = band.n();
+        let lower: Vec<f64> = (1..n).map(|i| band.get(i, i - 1)).collect();
+        let diagonal: Vec<f64> = (0..n).map(|i| band.get(i, i)).collect();
+        let upper: Vec<f64> = (1..n).map(|i| band.get(i - 1, i)).collect();
+
+        match thomas_solve(&lower, &diagonal, &upper, f) {
+            Ok(solution) => SolveReport::direct(solution),
+            Err(error) => SolveReport::failed(f.len(), error),
+        }
+    }
+}
+
+// блочно-трёхдиагональная матрица: lower[i] стоит под diagonal[i], upper[i] - над diagonal[i + 1]
+#[derive(Debug, Clone)]
+pub struct BlockTridiagonal {
+    lower: Vec<DMatrix<f64>>,
+    diagonal: Vec<DMatrix<f64>>,
+    upper: Vec<DMatrix<f64>>,
+}
+
+impl BlockTridiagonal {
+    // без блоков - пустая система, её решение - пустой вектор;
+    // в ошибке о форме блока value - номер этого блока
+    pub fn new(lower: Vec<DMatrix<f64>>, diagonal: Vec<DMatrix<f64>>, upper: Vec<DMatrix<f64>>) -> Result<Self, SolverError> {
+        let off_diagonal = diagonal.len().saturating_sub(1);
+        if lower.len() != off_diagonal || upper.len() != off_diagonal {
+            return Err(SolverError::InvalidParameter { name: "number of off-diagonal blocks", value: lower.len().max(upper.len()) as f64 });
+        }
+        if let Some(i) = diagonal.iter().position(|block| !block.is_square()) {
+            return Err(SolverError::InvalidParameter { name: "non-square diagonal block", value: i as f64 });
+        }
+        for i in 0..lower.len() {
+            if lower[i].shape() != (diagonal[i + 1].nrows(), diagonal[i].ncols()) {
+                return Err(SolverError::InvalidParameter { name: "lower block shape", value: i as f64 });
+            }
+            if upper[i].shape() != (diagonal[i].nrows(), diagonal[i + 1].ncols()) {
+                return Err(SolverError::InvalidParameter { name: "upper block shape", value: i as f64 });
+            }
+        }
+
+        Ok(BlockTridiagonal { lower, diagonal, upper })
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.diagonal.len()
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.diagonal.iter().map(|block| block.nrows()).sum()
+    }
+
+    fn offsets(&self) -> Vec<usize> {
+        let mut offsets = vec![0];
+        for block in &self.diagonal {
+            offsets.push(offsets.last().unwrap() + block.nrows());
+        }
+        offsets
+    }
+
+    pub fn mul_vec(&self, x: &DVector<f64>) -> DVector<f64> {
+        let offsets = self.offsets();
+        let part = |v: &DVector<f64>, i: usize| v.rows(offsets[i], self.diagonal[i].nrows()).into_owned();
+        let mut result = DVector::zeros(self.dimension());
+
+        for (i, diagonal) in self.diagonal.iter().enumerate() {
+            let mut block = diagonal * part(x, i);
+            if i > 0 {
+                block += &self.lower[i - 1] * part(x, i - 1);
+            }
+            if i + 1 < self.block_count() {
+                block += &self.upper[i] * part(x, i + 1);
+            }
+            result.rows_mut(offsets[i], block.len()).copy_from(&block);
+        }
+
+        result
+    }
+
+    // блочная прогонка: D'_i = D_i - L_{i-1} D'_{i-1}^{-1} U_{i-1}, каждый D'_i раскладывается LU,
+    // стоимость O(n b^2) при размере блока b
+    pub fn solve(&self, f: &DVector<f64>) -> Result<DVector<f64>, SolverError> {
+        if f.len() != self.dimension() {
+            return Err(SolverError::InvalidParameter { name: "right-hand side length", value: f.len() as f64 });
+        }
+        let offsets = self.offsets();
+        let count = self.block_count();
+        let part = |v: &DVector<f64>, i: usize| v.rows(offsets[i], self.diagonal[i].nrows()).into_owned();
+
+        let mut factorizations: Vec<LuFactorization> = Vec::with_capacity(count);
+        let mut reduced_f: Vec<DVector<f64>> = Vec::with_capacity(count);
+
+        for i in 0..count {
+            let (block, rhs) = if i == 0 {
+                (self.diagonal[0].clone(), part(f, 0))
+            } else {
+                let previous = &factorizations[i - 1];
+                let block = &self.diagonal[i] - &self.lower[i - 1] * previous.solve_many(&self.upper[i - 1])?;
+                let rhs = part(f, i) - &self.lower[i - 1] * previous.substitute(&reduced_f[i - 1]);
+                (block, rhs)
+            };
+
+            let factorization = LuFactorization::new(&block, Pivoting::Partial)?;
+            if factorization.is_singular() {
+                return Err(SolverError::ZeroPivot { row: offsets[i] });
+            }
+            factorizations.push(factorization);
+            reduced_f.push(rhs);
+        }
+
+        let mut x = DVector::zeros(self.dimension());
+        let mut next: Option<DVector<f64>> = None;
+        for i in (0..count).rev() {
+            let rhs = match &next {
+                Some(next) => &reduced_f[i] - &self.upper[i] * next,
+                None => reduced_f[i].clone(),
+            };
+            let block = factorizations[i].substitute(&rhs);
+            x.rows_mut(offsets[i], block.len()).copy_from(&block);
+            next = Some(block);
+        }
+
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::methods::generators::{create_poisson_1d, create_poisson_2d, create_poisson_2d_blocks, create_vector_f};
+    use crate::methods::solver::FactorizationError;
+
+    #[test]
+    fn thomas_matches_poisson_solution() {
+        let matrix = create_poisson_1d(20);
+        let x = DVector::from_fn(20, |i, _| (i as f64 * 0.3).sin());
+        let report = Thomas.solve(&matrix, &matrix.mul_vec(&x));
+        assert!((report.solution - x).norm() < 1e-10);
+    }
+
+    #[test]
+    fn thomas_rejects_wider_bands_and_zero_pivots() {
+        let report = Thomas.solve(&create_poisson_2d(3), &DVector::zeros(9));
+        assert!(matches!(report.error, Some(SolverError::NotTridiagonal { lower: 3, upper: 3 })));
+
+        let zero_pivot = thomas_solve(&[1.0], &[0.0, 1.0], &[1.0], &DVector::zeros(2));
+        assert!(matches!(zero_pivot, Err(SolverError::ZeroPivot { row: 0 })));
+    }
+
+    #[test]
+    fn inconsistent_sizes_are_errors() {
+        let report = Thomas.solve(&create_poisson_1d(4), &DVector::zeros(3));
+        assert!(matches!(report.error, Some(SolverError::InvalidParameter { name: "right-hand side length", .. })));
+        assert!(thomas_solve(&[1.0, 1.0], &[2.0, 2.0], &[1.0], &DVector::zeros(2)).is_err());
+
+        let report = Thomas.solve(&DMatrix::from_element(2, 3, 1.0), &DVector::zeros(2));
+        assert!(matches!(report.error, Some(SolverError::Factorization(FactorizationError::NotSquare { rows: 2, cols: 3 }))));
+
+        let block = DMatrix::identity(2, 2);
+        let wrong_lower = BlockTridiagonal::new(vec![DMatrix::identity(3, 2)], vec![block.clone(), block.clone()], vec![block.clone()]);
+        assert!(matches!(wrong_lower, Err(SolverError::InvalidParameter { name: "lower block shape", .. })));
+        assert!(BlockTridiagonal::new(vec![], vec![block.clone(), block.clone()], vec![]).is_err());
+
+        let blocks = create_poisson_2d_blocks(3).unwrap();
+        assert!(matches!(blocks.solve(&DVector::zeros(8)), Err(SolverError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn block_tridiagonal_matches_sparse_poisson() {
+        let blocks = create_poisson_2d_blocks(6).unwrap();
+        let matrix = create_poisson_2d(6);
+        let f = create_vector_f(36);
+
+        assert_eq!(blocks.dimension(), 36);
+        assert_eq!(blocks.mul_vec(&f), matrix.mul_vec(&f));
+        assert!((matrix.mul_vec(&blocks.solve(&f).unwrap()) - &f).norm() < 1e-10);
+    }
+
+    #[test]
+    fn empty_systems_have_empty_solutions() {
+        assert!(thomas_solve(&[], &[], &[], &DVector::zeros(0)).unwrap().is_empty());
+        assert!(Thomas.solve(&create_poisson_1d(0), &DVector::zeros(0)).solution.is_empty());
+
+        let blocks = create_poisson_2d_blocks(0).unwrap();
+        assert_eq!(blocks.block_count(), 0);
+        assert!(blocks.solve(&DVector::zeros(0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn zero_rhs_gives_zero_solution() {
+        assert_eq!(Thomas.solve(&create_poisson_1d(5), &DVector::zeros(5)).solution, DVector::zeros(5));
+
+        let blocks = create_poisson_2d_blocks(3).unwrap();
+        assert_eq!(blocks.solve(&DVector::zeros(9)).unwrap(), DVector::zeros(9));
+    }
+}
@@ -1,39 +1,299 @@
-use nalgebra::{DMatrix, DVector};
+use nalgebra::DVector;
 
-pub fn upper_relaxation_solve(matrix: &DMatrix<f64>, b: &DVector<f64>, omega: f64, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
-    let n = matrix.nrows();
-    let mut x = DVector::zeros(n); 
-    let mut residuals = Vec::new(); 
+use std::time::Instant;
 
-    for iteration in 0..max_iterations {
-        let mut x_new = x.clone(); 
+use super::diagnostics::{jacobi_iteration_spectral_radius, relaxation_precheck, ConvergenceError};
+use super::matrix::SystemMatrix;
+use super::observer::IterationObserver;
+use super::ordering::{color_classes, relaxation_sweep, Ordering, Sweep};
+use super::parallel::Execution;
+use super::solver::{replay, LinearSolver, SolveReport, SolverError, StopReason};
+use super::stopping::{announce, StoppingCriterion};
 
-        for i in 0..n {
-            let mut sum1 = 0.0;
-            let mut sum2 = 0.0;
+#[derive(Debug, Clone, PartialEq)]
+pub enum OmegaSelection {
+    Fixed(f64),
+    // omega = 2 / (1 + sqrt(1 - rho_J^2)), верно для согласованно упорядоченных матриц
+    Optimal,
+    // перебор по сетке, выбирается omega с наименьшим числом итераций
+    Sweep(Vec<f64>),
+}
+
+pub struct UpperRelaxation {
+    pub omega: OmegaSelection,
+    pub criterion: StoppingCriterion,
+    pub ordering: Ordering,
+    // Sweep::Symmetric даёт SSOR
+    pub sweep: Sweep,
+    // дешёвая проверка до начала итераций (диагональ, 0 < omega < 2), при переборе - для каждого omega
+    pub precheck: bool,
+}
+
+impl UpperRelaxation {
+    pub fn new(omega: f64, tolerance: f64, max_iterations: usize) -> Self {
+        UpperRelaxation { omega: OmegaSelection::Fixed(omega), criterion: StoppingCriterion::step_norm(tolerance, max_iterations), ordering: Ordering::Natural, sweep: Sweep::Forward, precheck: true }
+    }
+
+    pub fn with_selection(omega: OmegaSelection, tolerance: f64, max_iterations: usize) -> Self {
+        UpperRelaxation { omega, criterion: StoppingCriterion::step_norm(tolerance, max_iterations), ordering: Ordering::Natural, sweep: Sweep::Forward, precheck: true }
+    }
+
+    pub fn with_criterion(mut self, criterion: StoppingCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
 
-            for j in 0..i {
-                sum1 += matrix[(i, j)] * x_new[j];
+    pub fn without_precheck(mut self) -> Self {
+        self.precheck = false;
+        self
+    }
+
+    pub fn with_ordering(mut self, ordering: Ordering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    pub fn with_sweep(mut self, sweep: Sweep) -> Self {
+        self.sweep = sweep;
+        self
+    }
+
+    // точки сетки решаются молча, о сходимости сообщает только выбранный прогон
+    fn sweep(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>, omegas: &[f64], colors: Option<&[Vec<usize>]>) -> Vec<(f64, SolveReport)> {
+        let criterion = self.criterion.clone().quiet();
+        omegas.iter().map(|&omega| (omega, self.relaxation_solve(matrix, b, omega, colors, &criterion, &mut ()))).collect()
+    }
+
+    fn relaxation_solve(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>, omega: f64, colors: Option<&[Vec<usize>]>, criterion: &StoppingCriterion,
+        observer: &mut dyn IterationObserver) -> SolveReport {
+        let n = matrix.nrows();
+        if self.precheck {
+            if let Err(error) = relaxation_precheck(matrix, omega) {
+                let report = SolveReport::precheck_failed(n, error).with_omega(omega);
+                observer.on_finish(&report);
+                return report;
             }
+        }
 
-            for j in i + 1..n {
-                sum2 += matrix[(i, j)] * x[j];
+        let mut x = DVector::zeros(n);
+        let mut monitor = criterion.monitor(b).with_observer(observer);
+
+        for iteration in 0..monitor.max_iterations() {
+            let mut x_new = x.clone();
+
+            relaxation_sweep(matrix, b, &mut x_new, omega, colors, self.sweep, Execution::Serial);
+
+            let residual = b - matrix.mul_vec(&x_new);
+
+            if let Some(stop_reason) = monitor.check(residual.norm(), Some((&x_new - &x).norm())) {
+                monitor.announce("The upper relaxation method", iteration + 1, stop_reason);
+                return monitor.finish(x_new, iteration + 1, stop_reason).with_omega(omega);
             }
 
-            x_new[i] = (1.0 - omega) * x[i] + (omega * (b[i] - sum1 - sum2)) / matrix[(i, i)];
+            x = x_new;
         }
 
-        let residual = b - matrix * &x_new;
-        residuals.push(residual.norm());
+        let max_iterations = monitor.max_iterations();
+        monitor.announce("The upper relaxation method", max_iterations, StopReason::MaxIterations);
+        monitor.finish(x, max_iterations, StopReason::MaxIterations).with_omega(omega)
+    }
+}
+
+// формула неприменима, если на диагонали есть нули или метод Якоби расходится
+pub fn optimal_omega(matrix: &dyn SystemMatrix) -> Result<f64, ConvergenceError> {
+    if let Some(row) = matrix.diagonal().iter().position(|&value| value == 0.0) {
+        return Err(ConvergenceError::ZeroDiagonal { row });
+    }
+
+    let rho = jacobi_iteration_spectral_radius(matrix, 1e-10, 10000);
+    if rho >= 1.0 {
+        return Err(ConvergenceError::WillNotConverge { method: "Jacobi", spectral_radius: rho });
+    }
+
+    Ok(2.0 / (1.0 + (1.0 - rho * rho).sqrt()))
+}
+
+// omega_from..omega_to включительно, steps отрезков
+pub fn omega_grid(omega_from: f64, omega_to: f64, steps: usize) -> Vec<f64> {
+    (0..=steps).map(|k| omega_from + (omega_to - omega_from) * k as f64 / steps as f64).collect()
+}
+
+// результаты для каждого omega сетки: (omega, отчёт); ошибка - если порядок обхода неприменим к матрице
+pub fn sweep_omega(matrix: &dyn SystemMatrix, b: &DVector<f64>, omegas: &[f64], criterion: &StoppingCriterion, ordering: Ordering)
+    -> Result<Vec<(f64, SolveReport)>, SolverError> {
+    let solver = UpperRelaxation {
+        omega: OmegaSelection::Sweep(omegas.to_vec()),
+        criterion: criterion.clone(),
+        ordering,
+        sweep: Sweep::Forward,
+        precheck: true,
+    };
+    let colors = color_classes(matrix, ordering)?;
+    Ok(solver.sweep(matrix, b, omegas, colors.as_deref()))
+}
+
+impl LinearSolver for UpperRelaxation {
+    fn name(&self) -> &str {
+        "Upper relaxation"
+    }
 
-        if (&x_new - &x).norm() < tolerance {
-            println!("The upper relaxation method converged in {} iterations", iteration + 1);
-            return (x_new, residuals);
+    fn solve(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>) -> SolveReport {
+        self.solve_observed(matrix, b, &mut ())
+    }
+
+    // при переборе omega наблюдатель получает только историю лучшего прогона
+    fn solve_observed(&self, matrix: &dyn SystemMatrix, b: &DVector<f64>, observer: &mut dyn IterationObserver) -> SolveReport {
+        let n = matrix.nrows();
+        let colors = match color_classes(matrix, self.ordering) {
+            Ok(colors) => colors,
+            Err(error) => {
+                let report = SolveReport::failed(n, error);
+                observer.on_finish(&report);
+                return report;
+            }
+        };
+        match &self.omega {
+            OmegaSelection::Fixed(omega) => self.relaxation_solve(matrix, b, *omega, colors.as_deref(), &self.criterion, observer),
+            OmegaSelection::Optimal => match optimal_omega(matrix) {
+                Ok(omega) => self.relaxation_solve(matrix, b, omega, colors.as_deref(), &self.criterion, observer),
+                Err(error) => {
+                    let report = SolveReport::precheck_failed(n, error);
+                    observer.on_finish(&report);
+                    report
+                }
+            },
+            OmegaSelection::Sweep(omegas) => {
+                let start = Instant::now();
+                let best = self.sweep(matrix, b, omegas, colors.as_deref())
+                    .into_iter()
+                    .min_by_key(|(_, report)| (!report.converged, report.iterations));
+                let Some((_, report)) = best else {
+                    let report = SolveReport::failed(n, SolverError::InvalidParameter { name: "omega grid size", value: 0.0 });
+                    observer.on_finish(&report);
+                    return report;
+                };
+                if !self.criterion.quiet {
+                    announce("The upper relaxation method", report.iterations, report.stop_reason);
+                }
+                replay(observer, &report, start.elapsed());
+                report
+            }
         }
+    }
+}
+
+pub fn upper_relaxation_solve(matrix: &dyn SystemMatrix, b: &DVector<f64>, omega: f64, tolerance: f64, max_iterations: usize) -> (DVector<f64>, Vec<f64>) {
+    let report = UpperRelaxation::new(omega, tolerance, max_iterations).solve(matrix, b);
+    (report.solution, report.residuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
 
-        x = x_new;
+    use crate::methods::generators::{create_custom_sparse_matrix, create_poisson_1d, create_vector_f};
+
+    #[test]
+    fn solves_banded_system() {
+        let matrix = create_custom_sparse_matrix(50, 10.0);
+        let f = create_vector_f(50);
+        let report = UpperRelaxation::new(1.1, 1e-12, 1000).solve(&matrix, &f);
+
+        assert!(report.converged);
+        assert!((&f - matrix.mul_vec(&report.solution)).norm() < 1e-10);
     }
 
-    println!("Warning!!! The upper relaxation method did not converge for {} iterations.", max_iterations);
-    (x, residuals) 
+    // для трёхдиагональной матрицы rho_J = cos(pi / (n + 1))
+    #[test]
+    fn optimal_omega_matches_theory() {
+        let n = 30;
+        let rho = (std::f64::consts::PI / (n + 1) as f64).cos();
+        let expected = 2.0 / (1.0 + (1.0 - rho * rho).sqrt());
+        assert!((optimal_omega(&create_poisson_1d(n)).unwrap() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn optimal_omega_needs_convergent_jacobi() {
+        let zero_diagonal = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(optimal_omega(&zero_diagonal), Err(ConvergenceError::ZeroDiagonal { row: 0 }));
+
+        // rho_J = 2 для матрицы [[1, 2], [2, 1]]
+        let divergent = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+        let report = UpperRelaxation::with_selection(OmegaSelection::Optimal, 1e-10, 100).solve(&divergent, &DVector::from_element(2, 1.0));
+        assert!(matches!(optimal_omega(&divergent), Err(ConvergenceError::WillNotConverge { .. })));
+        assert_eq!(report.stop_reason, StopReason::PrecheckFailed);
+    }
+
+    #[test]
+    fn empty_omega_grid_and_non_bipartite_red_black_are_errors() {
+        let matrix = create_custom_sparse_matrix(10, 10.0);
+        let f = create_vector_f(10);
+
+        let report = UpperRelaxation::with_selection(OmegaSelection::Sweep(Vec::new()), 1e-10, 100).solve(&matrix, &f);
+        assert!(matches!(report.error, Some(SolverError::InvalidParameter { .. })));
+
+        let report = UpperRelaxation::new(1.2, 1e-10, 100).with_ordering(Ordering::RedBlack).solve(&matrix, &f);
+        assert!(matches!(report.error, Some(SolverError::NotBipartite)));
+        assert!(!report.converged);
+    }
+
+    #[test]
+    fn optimal_omega_beats_seidel() {
+        let matrix = create_poisson_1d(30);
+        let f = create_vector_f(30);
+        let criterion = StoppingCriterion::relative_residual(1e-8, 100000);
+        let seidel = UpperRelaxation::new(1.0, 0.0, 0).with_criterion(criterion.clone()).solve(&matrix, &f);
+        let optimal = UpperRelaxation::with_selection(OmegaSelection::Optimal, 0.0, 0).with_criterion(criterion).solve(&matrix, &f);
+
+        assert!(optimal.converged);
+        assert!(optimal.iterations < seidel.iterations);
+        assert_eq!(optimal.omega, Some(optimal_omega(&matrix).unwrap()));
+    }
+
+    #[test]
+    fn sweep_reports_every_omega() {
+        let matrix = create_custom_sparse_matrix(20, 10.0);
+        let omegas = omega_grid(0.5, 1.5, 4);
+        let criterion = StoppingCriterion::step_norm(1e-10, 1000);
+        let results = sweep_omega(&matrix, &create_vector_f(20), &omegas, &criterion, Ordering::Natural).unwrap();
+
+        assert_eq!(omegas, vec![0.5, 0.75, 1.0, 1.25, 1.5]);
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|(omega, report)| report.converged && report.omega == Some(*omega)));
+
+        // красно-чёрный обход требует двудольного графа матрицы
+        let error = sweep_omega(&matrix, &create_vector_f(20), &omegas, &criterion, Ordering::RedBlack);
+        assert!(matches!(error, Err(SolverError::NotBipartite)));
+    }
+
+    #[test]
+    fn zero_diagonal_fails_precheck() {
+        let matrix = DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0]);
+        let report = UpperRelaxation::new(1.5, 1e-10, 100).solve(&matrix, &DVector::from_element(2, 1.0));
+
+        assert_eq!(report.stop_reason, StopReason::PrecheckFailed);
+        assert!(report.solution.iter().all(|value| value.is_finite()));
+    }
+
+    #[test]
+    fn omega_outside_the_interval_fails_precheck() {
+        let report = UpperRelaxation::new(2.0, 1e-10, 100).solve(&create_poisson_1d(5), &create_vector_f(5));
+        assert_eq!(report.stop_reason, StopReason::PrecheckFailed);
+    }
+
+    #[test]
+    fn zero_rhs_and_empty_system() {
+        let criterion = StoppingCriterion::relative_residual(1e-10, 100);
+        let report = UpperRelaxation::new(1.2, 1e-10, 100).with_criterion(criterion.clone()).solve(&create_poisson_1d(10), &DVector::zeros(10));
+        assert!(report.converged);
+        assert_eq!(report.solution, DVector::zeros(10));
+
+        for selection in [OmegaSelection::Fixed(1.2), OmegaSelection::Optimal, OmegaSelection::Sweep(omega_grid(1.0, 1.5, 2))] {
+            let report = UpperRelaxation::with_selection(selection.clone(), 1e-10, 100).with_criterion(criterion.clone())
+                .solve(&DMatrix::zeros(0, 0), &DVector::zeros(0));
+            assert!(report.converged, "{:?}", selection);
+            assert!(report.solution.is_empty());
+        }
+    }
 }